@@ -7,7 +7,7 @@ use tetra::{input::Key, window, Context, Event, State};
 use crate::{
     assets::Assets,
     colors::Colors,
-    game::World,
+    game::{LogCategory, LogEvent, World},
     savefile,
     scenes::{Scene, SceneImpl, SomeTransitions, Transition},
     settings::Settings,
@@ -85,10 +85,34 @@ impl App {
         }
     }
 
-    fn load_world(&mut self, path: &Path) {
-        self.world = savefile::load_world(path)
-            .ok() // TODO: catch errors
-            .map(|w| Rc::new(RefCell::new(w)));
+    /// Returns false if the world can't be loaded, the error is shown instead
+    fn load_world(&mut self, ctx: &mut Context, path: &Path, salvage: bool) -> bool {
+        let result = if salvage {
+            savefile::salvage_world(path)
+        } else {
+            savefile::load_world(path).map(|world| (world, Vec::new()))
+        };
+        match result {
+            Ok((world, lost)) => {
+                let pos = world.player().pos;
+                for error in lost {
+                    world.log().push(LogEvent::new(
+                        format!("Lost from the savefile: {}", error),
+                        pos,
+                        LogCategory::Warning,
+                    ));
+                }
+                self.world = Some(Rc::new(RefCell::new(world)));
+                true
+            }
+            Err(error) => {
+                self.push_scene(
+                    ctx,
+                    Scene::LoadError(path.to_path_buf(), error.to_string(), !salvage),
+                );
+                false
+            }
+        }
     }
 
     fn unload_world(&mut self) {
@@ -98,12 +122,16 @@ impl App {
     fn exec_transitions(&mut self, ctx: &mut Context, transitions: SomeTransitions) {
         if let Some(transitions) = transitions {
             for transition in transitions {
-                self.transit(ctx, transition);
+                // next transitions need the world if it was loading
+                if !self.transit(ctx, transition) {
+                    break;
+                }
             }
         }
     }
 
-    fn transit(&mut self, ctx: &mut Context, transition: Transition) {
+    /// Returns false if the rest of transitions should be skipped
+    fn transit(&mut self, ctx: &mut Context, transition: Transition) -> bool {
         match transition {
             Transition::Push(s) => self.push_scene(ctx, s),
             Transition::Pop => self.pop_scene(ctx),
@@ -120,8 +148,10 @@ impl App {
                 self.scenes.drain(1..);
                 self.on_open(ctx);
             }
-            Transition::LoadWorld(path) => self.load_world(&path),
+            Transition::LoadWorld(path) => return self.load_world(ctx, &path, false),
+            Transition::SalvageWorld(path) => return self.load_world(ctx, &path, true),
         }
+        true
     }
 }
 
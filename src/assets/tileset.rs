@@ -15,6 +15,10 @@ static REGIONS: phf::Map<&'static str, Rectangle> = phf::phf_map! {
     "raw_zombie" => Rectangle::new(70.0, 0.0, 10.0, 10.0),
     "raw_zombie_child" => Rectangle::new(80.0, 0.0, 10.0, 10.0),
     "highlight" => Rectangle::new(90.0, 0.0, 10.0, 10.0),
+    "dog" => Rectangle::new(0.0, 80.0, 10.0, 10.0),
+    "zombie_dog" => Rectangle::new(10.0, 80.0, 10.0, 10.0),
+    "skeleton_dog" => Rectangle::new(20.0, 80.0, 10.0, 10.0),
+    "raw_zombie_dog" => Rectangle::new(30.0, 80.0, 10.0, 10.0),
     "dirt1" => Rectangle::new(0.0, 10.0, 10.0, 10.0),
     "dirt2" => Rectangle::new(10.0, 10.0, 10.0, 10.0),
     "dirt3" => Rectangle::new(20.0, 10.0, 10.0, 10.0),
@@ -62,6 +66,7 @@ static REGIONS: phf::Map<&'static str, Rectangle> = phf::phf_map! {
     "axe" => Rectangle::new(20.0, 70.0, 10.0, 10.0),
     "cloak" => Rectangle::new(30.0, 70.0, 10.0, 10.0),
     "hat" => Rectangle::new(40.0, 70.0, 10.0, 10.0),
+    "sack" => Rectangle::new(50.0, 70.0, 10.0, 10.0),
     "mt" => Rectangle::new(0.0, 90.0, 10.0, 10.0),
     "lt" => Rectangle::new(10.0, 90.0, 10.0, 10.0),
     "minus" => Rectangle::new(20.0, 90.0, 10.0, 10.0),
@@ -85,19 +90,14 @@ impl Tileset {
         })
     }
 
-    pub fn get_size(name: &'static str) -> Vec2 {
+    pub fn get_size(name: &str) -> Vec2 {
         REGIONS
             .get(name)
             .map(|r| Vec2::new(r.width, r.height))
             .unwrap_or_default()
     }
 
-    pub fn draw_region<P: Into<DrawParams>>(
-        &self,
-        ctx: &mut Context,
-        name: &'static str,
-        params: P,
-    ) {
+    pub fn draw_region<P: Into<DrawParams>>(&self, ctx: &mut Context, name: &str, params: P) {
         let region = REGIONS
             .get(name)
             .copied()
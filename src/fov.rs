@@ -81,6 +81,33 @@ pub fn field_of_view_set(center: Point, range: i32, fov_check: &Map) -> HashSet<
     visible_points
 }
 
+/// Nothing opaque stands between two points, the points themselves are not checked
+pub fn line_of_sight(from: Point, to: Point, fov_check: &dyn FovMap) -> bool {
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let step_x = (to.x - from.x).signum();
+    let step_y = (to.y - from.y).signum();
+    let mut error = dx + dy;
+    let mut current = from;
+    loop {
+        let e2 = error * 2;
+        if e2 >= dy {
+            error += dy;
+            current.x += step_x;
+        }
+        if e2 <= dx {
+            error += dx;
+            current.y += step_y;
+        }
+        if current == to {
+            return true;
+        }
+        if !fov_check.is_transparent(current) {
+            return false;
+        }
+    }
+}
+
 struct ScanFovData<'a> {
     center: Point,
     range_2: i32,
@@ -16,8 +16,10 @@ pub struct Action {
 
 impl Action {
     pub fn new(owner: usize, typ: ActionType, world: &World) -> Result<Self, String> {
-        match typ.is_possible(world.get_unit(owner), world) {
+        match typ.is_possible(owner, world) {
             ActionPossibility::Yes(length) => {
+                let k = world.get_unit(owner).fatigue_k();
+                let length = (length as f32 * k).round() as u32;
                 let finish = world.meta.current_tick + length as u128;
                 Ok(Self {
                     owner,
@@ -38,8 +40,23 @@ impl Action {
         world.get_unit_mut(self.owner)
     }
 
-    fn cancel_action(&self, world: &mut World, reason: String) {
-        self.owner_mut(world).action = None;
+    /// Part of the action that is already done, from 0.0 to 1.0
+    pub fn progress(&self, current_tick: u128) -> f32 {
+        let left = self.finish.saturating_sub(current_tick) as f32;
+        1.0 - left / self.length.max(1) as f32
+    }
+
+    /// How many ticks were already spent on the action
+    pub fn ticks_done(&self, current_tick: u128) -> u32 {
+        self.length - self.finish.saturating_sub(current_tick) as u32
+    }
+
+    /// Stops the action and everything queued after it
+    pub fn cancel(&self, world: &mut World, reason: String) {
+        self.typ.on_cancel(self, world);
+        let owner = self.owner_mut(world);
+        owner.action = None;
+        owner.action_queue.clear();
         if self.owner == 0 {
             world.log().push(LogEvent::new(
                 reason,
@@ -51,15 +68,16 @@ impl Action {
 
     /// called every tick
     pub fn act(&self, world: &mut World) {
-        if let ActionPossibility::No(reason) = self.typ.is_possible(self.owner(world), world) {
-            self.cancel_action(world, reason);
+        if let ActionPossibility::No(reason) = self.typ.is_possible(self.owner, world) {
+            self.cancel(world, reason);
+            return;
         }
-        // TODO: draw stamina
-
         let steps = (self.finish - world.meta.current_tick) as u32;
         if steps == self.length {
             self.typ.on_start(self, world);
         } else if steps == 0 {
+            let cost = self.typ.stamina_cost(self, world);
+            self.owner_mut(world).spend_stamina(cost);
             self.typ.on_finish(self, world);
         } else {
             self.typ.on_step(self, world);
@@ -1,14 +1,18 @@
 use enum_dispatch::enum_dispatch;
 
-use super::{
-    super::{Avatar, World},
-    Action, ActionPossibility,
-};
+use super::{super::World, Action, ActionPossibility};
 
 #[enum_dispatch]
 pub trait ActionImpl {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility;
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility;
+    /// Stamina spent when the action is finished, negative for resting.
+    /// Any work is a bit tiring.
+    fn stamina_cost(&self, _action: &Action, _world: &World) -> i32 {
+        1
+    }
     fn on_start(&self, _action: &Action, _world: &mut World) {}
     fn on_step(&self, _action: &Action, _world: &mut World) {}
     fn on_finish(&self, _action: &Action, _world: &mut World) {}
+    /// Called when the action is interrupted before it's finished
+    fn on_cancel(&self, _action: &Action, _world: &mut World) {}
 }
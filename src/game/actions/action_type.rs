@@ -2,12 +2,15 @@ use enum_dispatch::enum_dispatch;
 
 use super::{
     super::{Avatar, World},
-    implements::{Dig, Drop, Raise, Read, Skip, Walk, Wield},
+    implements::{
+        Attach, Attack, Dig, Dismember, Drop, PickUp, PutIn, Raise, Read, Skip, TakeOff, TakeOut,
+        Walk, Wear, Wield,
+    },
     Action, ActionImpl, ActionPossibility,
 };
 
 #[enum_dispatch(ActionImpl)]
-#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub enum ActionType {
     Skip,
     Walk,
@@ -15,7 +18,15 @@ pub enum ActionType {
     Drop,
     Dig,
     Read,
-    Raise, // TODO: write test for animate
+    Raise,
+    Dismember,
+    Attach,
+    Attack,
+    PickUp,
+    PutIn,
+    TakeOut,
+    Wear,
+    TakeOff,
 }
 
 #[cfg(test)]
@@ -24,16 +35,26 @@ mod tests {
 
     use super::{
         super::super::{
-            bodies::{Freshness, OrganData},
-            human::{tests::personality::dead_boy, Gender, MainHand, SkinTone},
+            animals::{helpers::dog_body, AnimalIdentity, FurColor},
+            bodies::{BodyPartPath, BodySize, Freshness, OrganData, Sex},
+            human::{
+                helpers::{human_body, human_centipede},
+                tests::personality::dead_boy,
+                Gender, MainHand, SkinTone,
+            },
             map::{
-                items::{Axe, BodyPart, BodyPartType, Gravestone, Shovel},
+                items::{
+                    Axe, BodyPart, BodyPartType, Corpse, Gravestone, Hat, Identity, Knife, Sack,
+                    Shovel,
+                },
                 terrains::{Boulder, BoulderSize, Dirt, Grave, GraveData, GraveVariant},
-                Item, Terrain,
+                Item, ItemView, Terrain,
             },
             world::tests::{add_zombie, prepare_world},
+            Avatar,
         },
-        Action, Dig, Drop, Read, Skip, Walk, Wield,
+        Action, Attach, Attack, Dig, Dismember, Drop, PickUp, PutIn, Raise, Read, Skip, TakeOff,
+        TakeOut, Walk, Wear, Wield,
     };
 
     #[test]
@@ -157,6 +178,129 @@ mod tests {
         assert!(matches!(item, Item::Axe(..)));
     }
 
+    #[test]
+    fn test_containers() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.player_mut().wield.push(Sack::new().into());
+        world.map().get_tile_mut(Point::new(1, 0)).items.clear();
+        let character = dead_boy();
+        let body = human_body(&character, Freshness::Fresh);
+        world
+            .map()
+            .get_tile_mut(Point::new(1, 0))
+            .items
+            .push(Corpse::new(character, body).into());
+
+        let typ = PickUp {
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        assert!(world.map().get_tile(Point::new(1, 0)).items.is_empty());
+        assert_eq!(1, world.player().wield.len());
+        let sack = world.player().containers().next().unwrap();
+        assert!(matches!(sack.items.first(), Some(Item::Corpse(..))));
+
+        world.player_mut().wield.push(Knife::new().into());
+        world.player_mut().action =
+            Some(Action::new(0, PutIn { item_id: 1 }.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        assert_eq!(1, world.player().wield.len());
+        let sack = world.player().containers().next().unwrap();
+        assert_eq!(2, sack.items.len());
+        assert!(matches!(sack.items.last(), Some(Item::Knife(..))));
+
+        let typ = TakeOut {
+            container_id: 0,
+            item_id: 0,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        assert_eq!(2, world.player().wield.len());
+        assert!(matches!(
+            world.player().wield.last(),
+            Some(Item::Corpse(..))
+        ));
+        let sack = world.player().containers().next().unwrap();
+        assert_eq!(1, sack.items.len());
+
+        assert!(Action::new(0, typ.into(), &world).is_err());
+    }
+
+    #[test]
+    fn test_wearing() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.map().get_tile_mut(Point::new(1, 0)).items.clear();
+        let character = dead_boy();
+        let body = human_body(&character, Freshness::Rotten);
+        world
+            .map()
+            .get_tile_mut(Point::new(1, 0))
+            .items
+            .push(Corpse::new(character, body).into());
+
+        let hat_id = world
+            .player()
+            .body
+            .wear
+            .iter()
+            .position(|i| matches!(i, Item::Hat(..)))
+            .unwrap();
+        let typ = TakeOff {
+            item_id: hat_id,
+            dir: Direction::Here,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        assert!(matches!(world.player().wield.first(), Some(Item::Hat(..))));
+        assert!(!world
+            .player()
+            .body
+            .wear
+            .iter()
+            .any(|i| matches!(i, Item::Hat(..))));
+
+        let typ = Wear {
+            item_id: 0,
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        assert!(world.player().wield.is_empty());
+        assert!(matches!(
+            world.map().get_tile(Point::new(1, 0)).items.first(),
+            Some(Item::Corpse(corpse)) if corpse.name() == "rotten boy corpse"
+        ));
+
+        world.player_mut().wield.push(Hat::new().into());
+        assert!(Action::new(0, typ.into(), &world).is_err());
+
+        let head = BodyPartPath::new(Point::new(0, 0), vec!["head".to_string()]);
+        world.with_body_at(Point::new(1, 0), |body| {
+            let body = body.unwrap();
+            body.wear.clear();
+            body.detach(&head).unwrap();
+        });
+        assert!(Action::new(0, typ.into(), &world).is_err());
+    }
+
     #[test]
     fn test_skipping_time() {
         let mut world = prepare_world();
@@ -260,7 +404,10 @@ mod tests {
         assert!(corpse.is_some());
         if let Some(corpse) = corpse {
             if let Item::Corpse(corpse) = corpse {
-                let ch = &corpse.character;
+                let ch = match &corpse.character {
+                    Identity::Human(ch) => ch,
+                    Identity::Animal(..) => unreachable!(),
+                };
                 let body = &corpse.body;
                 assert_eq!("Dead Boy", ch.mind.name);
                 assert_eq!(SkinTone::Almond, ch.appearance.skin_tone);
@@ -303,6 +450,147 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_interrupted_digging() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.player_mut().wield.push(Shovel::new().into());
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Dirt::default().into();
+
+        let typ = Dig {
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        world.tick();
+        let progress = world
+            .player()
+            .action
+            .as_ref()
+            .unwrap()
+            .progress(world.meta.current_tick);
+        assert!(progress > 0.0 && progress < 1.0);
+
+        world.interrupt_player("Stop");
+        assert!(world.player().action.is_none());
+        let dug = world.map().get_tile(Point::new(1, 0)).dug;
+        assert!(dug > 0);
+        assert!(matches!(
+            world.map().get_tile(Point::new(1, 0)).terrain,
+            Terrain::Dirt(..)
+        ));
+
+        let action = Action::new(0, typ.into(), &world).unwrap();
+        assert_eq!(1000 - dug, action.length);
+        world.player_mut().action = Some(action);
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        assert!(matches!(
+            world.map().get_tile(Point::new(1, 0)).terrain,
+            Terrain::Pit(..)
+        ));
+        assert_eq!(0, world.map().get_tile(Point::new(1, 0)).dug);
+    }
+
+    #[test]
+    fn test_exhausted_digging() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.player_mut().wield.push(Shovel::new().into());
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Dirt::default().into();
+        world.player_mut().stamina = 0;
+
+        let typ = Dig {
+            dir: Direction::East,
+        };
+        let action = Action::new(0, typ.into(), &world).unwrap();
+        assert_eq!(2000, action.length);
+        world.player_mut().action = Some(action.clone());
+        for _ in 0..100 {
+            world.tick();
+        }
+        let done = action.ticks_done(world.meta.current_tick);
+        world.interrupt_player("Stop");
+        // twice as slow as a rested digger
+        assert_eq!(done / 2, world.map().get_tile(Point::new(1, 0)).dug);
+    }
+
+    #[test]
+    fn test_stamina() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.player_mut().wield.push(Shovel::new().into());
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Dirt::default().into();
+        world.map().get_tile_mut(Point::new(-1, 0)).terrain = Dirt::default().into();
+
+        let walk = Walk {
+            dir: Direction::West,
+        };
+        let rested_length = Action::new(0, walk.into(), &world).unwrap().length;
+
+        let typ = Dig {
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        let stamina = world.player().stamina;
+        assert!(stamina < 100);
+
+        // one point for five ticks of rest
+        for _ in 0..5 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert_eq!(stamina + 1, world.player().stamina);
+
+        world.player_mut().stamina = 0;
+        let tired_length = Action::new(0, walk.into(), &world).unwrap().length;
+        assert!(tired_length > rested_length);
+
+        // tired digger is slower, but the same work costs the same
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Dirt::default().into();
+        world.player_mut().stamina = 40;
+        let action = Action::new(0, typ.into(), &world).unwrap();
+        assert!(action.length > 1000);
+        world.player_mut().action = Some(action);
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        assert_eq!(40 - 1000 / 40, world.player().stamina);
+    }
+
+    #[test]
+    fn test_walking_stamina() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        for x in 1..=2 {
+            world.map().get_tile_mut(Point::new(x, 0)).terrain = Dirt::default().into();
+        }
+        let walk = Walk {
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, walk.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        assert_eq!(99, world.player().stamina);
+
+        // things in worn pockets are carried too
+        for _ in 0..12 {
+            let pockets = world.player_mut().container_mut(0).unwrap();
+            pockets.items.push(Axe::new().into());
+        }
+        let carried = world.player().carried_mass();
+        assert!(carried >= 10_000);
+        world.player_mut().action = Some(Action::new(0, walk.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        assert_eq!(99 - 1 - (carried / 10_000) as u8, world.player().stamina);
+    }
+
     #[test]
     fn test_reading() {
         let mut world = prepare_world();
@@ -353,4 +641,242 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_dismembering() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.map().get_tile_mut(Point::new(1, 0)).items.clear();
+        let character = dead_boy();
+        let body = human_body(&character, Freshness::Rotten);
+        world
+            .map()
+            .get_tile_mut(Point::new(1, 0))
+            .items
+            .push(Corpse::new(character, body).into());
+
+        let typ = Dismember {
+            dir: Direction::East,
+            path: BodyPartPath::new(Point::new(0, 0), vec!["left arm".to_string()]),
+        };
+        assert!(Action::new(0, typ.clone().into(), &world).is_err());
+
+        world.player_mut().wield.push(Knife::new().into());
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        let mut map = world.map();
+        let items = &map.get_tile(Point::new(1, 0)).items;
+        assert_eq!(2, items.len());
+        assert!(matches!(
+            &items[0],
+            Item::Corpse(corpse) if corpse.name() == "naked one-armed rotten boy corpse"
+        ));
+        assert!(matches!(&items[1], Item::BodyPart(part) if part.name == "left arm"));
+    }
+
+    #[test]
+    fn test_attaching() {
+        let mut world = prepare_world();
+        world.player_mut().wield.clear();
+        world.map().get_tile_mut(Point::new(1, 0)).items.clear();
+        let character = dead_boy();
+        let mut body = human_body(&character, Freshness::Rotten);
+        let torso = BodyPartPath::root(Point::new(0, 0));
+        let arm = body.detach(&torso.child("left arm")).unwrap();
+        let head = body.get_part(&torso.child("head")).unwrap().clone();
+        world
+            .map()
+            .get_tile_mut(Point::new(1, 0))
+            .items
+            .push(Corpse::new(character, body).into());
+
+        let typ = Attach {
+            dir: Direction::East,
+            parent: torso.clone(),
+            inside: false,
+        };
+        assert!(Action::new(0, typ.clone().into(), &world).is_err());
+
+        world.player_mut().wield.push(arm.into());
+        world.player_mut().action = Some(Action::new(0, typ.clone().into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        assert!(world.player().wield.is_empty());
+        assert!(matches!(
+            &world.map().get_tile(Point::new(1, 0)).items[0],
+            Item::Corpse(corpse) if corpse.name() == "naked rotten boy corpse"
+        ));
+
+        world.player_mut().wield.push(head.into());
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+        let mut map = world.map();
+        if let Item::Corpse(corpse) = &map.get_tile(Point::new(1, 0)).items[0] {
+            let second_head = torso.child("second head");
+            assert!(corpse.body.get_part(&second_head).is_some());
+            assert!(!corpse.body.is_misplaced(&second_head));
+            assert!(!corpse.body.is_misplaced(&torso.child("head")));
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[test]
+    fn test_limping() {
+        let mut world = prepare_world();
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Dirt::default().into();
+        let typ = Walk {
+            dir: Direction::East,
+        };
+        let normal = Action::new(0, typ.into(), &world).unwrap().length;
+
+        let torso = BodyPartPath::root(Point::new(0, 0));
+        world.player_mut().body.detach(&torso.child("left leg"));
+        let limping = Action::new(0, typ.into(), &world).unwrap().length;
+        assert!(limping > normal);
+
+        world.player_mut().body.detach(&torso.child("right leg"));
+        let crawling = Action::new(0, typ.into(), &world).unwrap().length;
+        assert!(crawling > limping);
+    }
+
+    #[test]
+    fn test_attacking() {
+        let mut world = prepare_world();
+        for pos in [Point::new(2, 0), Point::new(1, 1), Point::new(1, -1)] {
+            world.map().get_tile_mut(pos).terrain = Boulder::new(BoulderSize::Huge).into();
+        }
+        world.map().get_tile_mut(Point::new(1, 0)).items.clear();
+        let zombie = add_zombie(&mut world, Point::new(1, 0));
+        world.player_mut().wield.clear();
+        world.player_mut().wield.push(Axe::new().into());
+
+        let typ = Attack {
+            dir: Direction::East,
+        };
+        for _ in 0..200 {
+            if let Ok(action) = Action::new(0, typ.into(), &world) {
+                world.player_mut().action = Some(action);
+                while world.player().action.is_some() {
+                    world.tick();
+                }
+            } else {
+                break;
+            }
+        }
+
+        assert!(world.get_unit(zombie).dead);
+        assert!(world
+            .map()
+            .get_tile(Point::new(1, 0))
+            .items
+            .iter()
+            .any(|i| matches!(i, Item::Corpse(..))));
+        assert!(Action::new(0, typ.into(), &world).is_err());
+    }
+
+    #[test]
+    fn test_attacking_own_segment() {
+        let mut world = prepare_world();
+        let body = human_centipede(vec![dead_boy(), dead_boy()]);
+        let centipede = world.add_unit(Avatar::zombie(dead_boy(), body, Point::new(5, 0)));
+        let typ = Attack {
+            dir: Direction::West,
+        };
+        assert!(Action::new(centipede, typ.into(), &world).is_err());
+
+        let zombie = add_zombie(&mut world, Point::new(4, 0));
+        let typ = Attack {
+            dir: Direction::West,
+        };
+        assert!(Action::new(centipede, typ.into(), &world).is_ok());
+        // the lowest id wins when several zombies share the tile
+        assert_eq!(
+            Some(centipede.min(zombie)),
+            world.zombie_at(Point::new(4, 0))
+        );
+    }
+
+    #[test]
+    fn test_raising_dog() {
+        let mut world = prepare_world();
+        world.map().get_tile_mut(Point::new(1, 0)).items.clear();
+        let identity = AnimalIdentity::new(3, Sex::Male, BodySize::Normal, FurColor::Black, false);
+        let body = dog_body(&identity, Freshness::Rotten);
+        world
+            .map()
+            .get_tile_mut(Point::new(1, 0))
+            .items
+            .push(Corpse::animal(identity, body).into());
+        assert_eq!(
+            "rotten dog corpse",
+            world.map().get_tile(Point::new(1, 0)).items[0].name()
+        );
+
+        let typ = Raise {
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        let tile_units = world.map().get_tile(Point::new(1, 0)).units.clone();
+        assert!(world.map().get_tile(Point::new(1, 0)).items.is_empty());
+        let dog = *tile_units.iter().next().unwrap();
+        assert!(world.get_unit(dog).is_zombie());
+        assert_eq!("Zombie dog", world.get_unit(dog).name_for_actions());
+
+        let zombie = add_zombie(&mut world, Point::new(1, 1));
+        for y in 0..=1 {
+            world.map().get_tile_mut(Point::new(2, y)).terrain = Dirt::default().into();
+        }
+        let walk = Walk {
+            dir: Direction::East,
+        };
+        let dog_walk = Action::new(dog, walk.into(), &world).unwrap();
+        let zombie_walk = Action::new(zombie, walk.into(), &world).unwrap();
+        assert!(dog_walk.length < zombie_walk.length);
+    }
+
+    #[test]
+    fn test_raising_centipede() {
+        let mut world = prepare_world();
+        for x in -1..=0 {
+            let mut map = world.map();
+            let tile = map.get_tile_mut(Point::new(x, 1));
+            tile.terrain = Dirt::default().into();
+            tile.items.clear();
+        }
+        let body = human_centipede(vec![dead_boy(), dead_boy()]);
+        world
+            .map()
+            .get_tile_mut(Point::new(0, 1))
+            .items
+            .push(Corpse::new(dead_boy(), body).into());
+        // the tail lies to the west of the head
+        world.map().get_tile_mut(Point::new(-1, 1)).terrain =
+            Boulder::new(BoulderSize::Huge).into();
+
+        let typ = Raise {
+            dir: Direction::South,
+        };
+        assert!(Action::new(0, typ.into(), &world).is_err());
+
+        world.map().get_tile_mut(Point::new(-1, 1)).terrain = Dirt::default().into();
+        world.player_mut().action = Some(Action::new(0, typ.into(), &world).unwrap());
+        while world.player().action.is_some() {
+            world.tick();
+        }
+
+        let centipede = world.zombie_at(Point::new(0, 1)).unwrap();
+        assert_eq!(Some(centipede), world.zombie_at(Point::new(-1, 1)));
+        assert!(world.map().get_tile(Point::new(0, 1)).items.is_empty());
+    }
 }
@@ -0,0 +1,126 @@
+use geometry::Direction;
+
+use super::super::{
+    super::{
+        bodies::BodyPartPath,
+        log::{LogCategory, LogEvent},
+        map::item::{Item, ItemInteract, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Attach {
+    pub dir: Direction,
+    pub parent: BodyPartPath,
+    pub inside: bool,
+}
+
+impl ActionImpl for Attach {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        let part = actor.wield.iter().find_map(|i| match i {
+            Item::BodyPart(part) => Some(part),
+            _ => None,
+        });
+        let length = if let Some(part) = part {
+            ((part.mass() as f64 / 20.0).round() as u32).max(20)
+        } else {
+            return No("You need to hold a body part to attach it".to_string());
+        };
+        let pos = actor.pos + self.dir;
+        if let Some(unit_id) = world.zombie_at(pos) {
+            let unit = world.get_unit(unit_id);
+            return if unit.body.get_part(&self.parent).is_some() {
+                Yes(length)
+            } else {
+                No(format!("{} has no such body part", unit.name_for_actions()))
+            };
+        }
+        let mut map = world.map();
+        let corpse = map.get_tile(pos).items.iter().find_map(|i| match i {
+            Item::Corpse(corpse) => Some(corpse),
+            _ => None,
+        });
+        if let Some(corpse) = corpse {
+            if corpse.body.get_part(&self.parent).is_some() {
+                Yes(length)
+            } else {
+                No(format!("The {} has no such body part", corpse.name()))
+            }
+        } else {
+            No("There is nothing to attach it to".to_string())
+        }
+    }
+
+    fn on_start(&self, action: &Action, world: &mut World) {
+        let owner = action.owner(world);
+        world.log().push(LogEvent::new(
+            format!("{} started stitching", owner.name_for_actions()),
+            owner.pos,
+            LogCategory::Info,
+        ));
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let pos = action.owner(world).pos + self.dir;
+        let has_parent = world.inspect_body_at(
+            pos,
+            |body| matches!(body, Some(body) if body.get_part(&self.parent).is_some()),
+        );
+        if !has_parent {
+            return;
+        }
+        let owner = action.owner_mut(world);
+        let i = if let Some(i) = owner
+            .wield
+            .iter()
+            .position(|i| matches!(i, Item::BodyPart(..)))
+        {
+            i
+        } else {
+            return;
+        };
+        let part = match owner.wield.remove(i) {
+            Item::BodyPart(part) => part,
+            item => {
+                owner.wield.insert(i, item);
+                return;
+            }
+        };
+        let name = part.name();
+        let attached = world.with_body_at(pos, |body| match body {
+            Some(body) if body.get_part(&self.parent).is_some() => {
+                let path = body.attach(&self.parent, part, self.inside);
+                Ok(path.map_or(false, |path| body.is_misplaced(&path)))
+            }
+            _ => Err(part),
+        });
+        let misplaced = match attached {
+            Ok(misplaced) => misplaced,
+            Err(part) => {
+                // nothing to stitch to, the part stays in hands
+                action.owner_mut(world).wield.insert(i, part.into());
+                return;
+            }
+        };
+        world.log().push(LogEvent::new(
+            format!(
+                "{} attached the {}",
+                action.owner(world).name_for_actions(),
+                name
+            ),
+            pos,
+            LogCategory::Success,
+        ));
+        if misplaced {
+            world.log().push(LogEvent::new(
+                format!("The {} doesn't really belong there", name),
+                pos,
+                LogCategory::Warning,
+            ));
+        }
+    }
+}
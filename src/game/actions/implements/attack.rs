@@ -0,0 +1,95 @@
+use geometry::Direction;
+
+use super::super::{
+    super::{
+        bodies::{Damage, DamageType},
+        log::{LogCategory, LogEvent},
+        map::item::{ItemInteract, ItemView},
+        Avatar, World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct Attack {
+    pub dir: Direction,
+}
+
+impl Attack {
+    /// First wielded weapon or the best natural one, with time needed for a blow
+    fn weapon(actor: &Avatar) -> Option<(String, Damage, u32)> {
+        if let Some((item, damage)) = actor
+            .wield
+            .iter()
+            .find_map(|i| i.melee_damage().map(|damage| (i, damage)))
+        {
+            return Some((
+                format!("the {}", item.name()),
+                damage,
+                20 + item.mass() / 100,
+            ));
+        }
+        actor.body.capabilities().natural_weapon().map(|damage| {
+            let name = match damage.typ {
+                DamageType::Bite => "teeth",
+                _ => "fists",
+            };
+            (name.to_string(), damage, 15)
+        })
+    }
+
+    /// Unit with the lowest id on the tile, segments of a long actor are not targets
+    fn target(&self, actor_id: usize, world: &World) -> Option<usize> {
+        let pos = world.get_unit(actor_id).pos + self.dir;
+        let units: Vec<usize> = world.map().get_tile(pos).units.iter().copied().collect();
+        units.into_iter().filter(|&i| i != actor_id).min()
+    }
+}
+
+impl ActionImpl for Attack {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        if self.dir.is_here() {
+            return No("You can't attack yourself".to_string());
+        }
+        if self.target(actor_id, world).is_none() {
+            return No("There is no one to attack".to_string());
+        }
+        if let Some((.., length)) = Self::weapon(actor) {
+            Yes(length)
+        } else {
+            No("You have nothing to fight with".to_string())
+        }
+    }
+
+    fn stamina_cost(&self, _action: &Action, _world: &World) -> i32 {
+        3
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let owner = action.owner(world);
+        let (target, (weapon, damage, _)) = if let (Some(target), Some(weapon)) =
+            (self.target(action.owner, world), Self::weapon(owner))
+        {
+            (target, weapon)
+        } else {
+            return;
+        };
+        let unit = world.get_unit(target);
+        world.log().push(LogEvent::new(
+            format!(
+                "{} attacked {} with {}",
+                owner.name_for_actions(),
+                unit.name_as_object(),
+                weapon
+            ),
+            unit.pos,
+            LogCategory::Warning,
+        ));
+        let path = unit.body.random_hit_location(&mut *world.rng());
+        if let Some(path) = path {
+            world.damage_unit(target, &path, damage);
+        }
+    }
+}
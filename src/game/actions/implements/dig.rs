@@ -7,8 +7,9 @@ use super::super::{
         map::{
             item::{ItemInteract, ItemTag},
             terrain::{Terrain, TerrainInteract, TerrainView},
+            Tile,
         },
-        Avatar, World,
+        World,
     },
     Action, ActionImpl,
     ActionPossibility::{self, No, Yes},
@@ -19,8 +20,18 @@ pub struct Dig {
     pub dir: Direction,
 }
 
+/// Ticks a rested digger needs to finish the hole, half-dug holes are easier to finish
+fn dig_length(tile: &Tile) -> u32 {
+    let length: u32 = match tile.terrain {
+        Terrain::Grave(..) => 2000,
+        _ => 1000,
+    };
+    length.saturating_sub(tile.dug).max(1)
+}
+
 impl ActionImpl for Dig {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
         let pos = actor.pos + self.dir;
         let mut map = world.map();
         let tile = map.get_tile(pos);
@@ -31,10 +42,7 @@ impl ActionImpl for Dig {
             return No("You need a shovel to dig!".to_string());
         }
 
-        Yes(match tile.terrain {
-            Terrain::Grave(..) => 2000,
-            _ => 1000,
-        })
+        Yes(dig_length(tile))
     }
 
     fn on_start(&self, action: &Action, world: &mut World) {
@@ -46,11 +54,29 @@ impl ActionImpl for Dig {
         ));
     }
 
+    /// Tired digger is slower but the work is the same, so is the cost
+    fn stamina_cost(&self, action: &Action, world: &World) -> i32 {
+        let pos = action.owner(world).pos + self.dir;
+        let length = dig_length(world.map().get_tile(pos));
+        (length / 40) as i32
+    }
+
+    fn on_cancel(&self, action: &Action, world: &mut World) {
+        let pos = action.owner(world).pos + self.dir;
+        let done = action.ticks_done(world.meta.current_tick);
+        // tired digger needs more ticks for the same work, `dug` counts ticks of a rested one
+        let length = dig_length(world.map().get_tile(pos));
+        let dug = (u64::from(done) * u64::from(length) / u64::from(action.length.max(1))) as u32;
+        if dug > 0 {
+            world.map().get_tile_mut(pos).dug += dug;
+        }
+        action.owner_mut(world).spend_stamina((dug / 40) as i32);
+    }
+
     fn on_finish(&self, action: &Action, world: &mut World) {
         let pos = action.owner(world).pos + self.dir;
         let items = world.map().get_tile_mut(pos).dig();
         if !items.is_empty() {
-            let mut rng = rand::thread_rng();
             let places: Vec<Direction> = DIR8
                 .iter()
                 .copied()
@@ -60,7 +86,7 @@ impl ActionImpl for Dig {
                 })
                 .collect();
             for item in items {
-                let delta = places.choose(&mut rng).copied().unwrap();
+                let delta = places.choose(&mut *world.rng()).copied().unwrap();
                 world.map().get_tile_mut(pos + delta).items.push(item);
             }
         }
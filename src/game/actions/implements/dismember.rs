@@ -0,0 +1,92 @@
+use geometry::Direction;
+
+use super::super::{
+    super::{
+        bodies::BodyPartPath,
+        log::{LogCategory, LogEvent},
+        map::item::{Item, ItemInteract, ItemTag, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Dismember {
+    pub dir: Direction,
+    pub path: BodyPartPath,
+}
+
+impl ActionImpl for Dismember {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        let speed = actor
+            .wield
+            .iter()
+            .filter(|i| i.tags().contains(&ItemTag::Butch))
+            .map(ItemInteract::butch_speed)
+            .fold(0.0, f64::max);
+        if speed <= 0.0 {
+            return No("You need a knife or an axe to dismember".to_string());
+        }
+        if self.path.is_root() {
+            return No("You can't cut off the whole body".to_string());
+        }
+        let pos = actor.pos + self.dir;
+        let mut map = world.map();
+        let corpse = map.get_tile(pos).items.iter().find_map(|i| match i {
+            Item::Corpse(corpse) => Some(corpse),
+            _ => None,
+        });
+        if let Some(corpse) = corpse {
+            if let Some(part) = corpse.body.get_part(&self.path) {
+                Yes(((part.mass() as f64 / speed).round() as u32).max(10))
+            } else {
+                No(format!("The {} has no such body part", corpse.name()))
+            }
+        } else {
+            No("There is nothing to dismember".to_string())
+        }
+    }
+
+    fn on_start(&self, action: &Action, world: &mut World) {
+        let owner = action.owner(world);
+        world.log().push(LogEvent::new(
+            format!("{} started dismembering", owner.name_for_actions()),
+            owner.pos,
+            LogCategory::Info,
+        ));
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let pos = action.owner(world).pos + self.dir;
+        let name = {
+            let mut map = world.map();
+            let tile = map.get_tile_mut(pos);
+            let part = tile
+                .items
+                .iter_mut()
+                .find_map(|i| match i {
+                    Item::Corpse(corpse) => Some(corpse),
+                    _ => None,
+                })
+                .and_then(|corpse| corpse.body.detach(&self.path));
+            part.map(|part| {
+                let name = part.name();
+                tile.items.push(part.into());
+                name
+            })
+        };
+        if let Some(name) = name {
+            world.log().push(LogEvent::new(
+                format!(
+                    "{} cut off the {}",
+                    action.owner(world).name_for_actions(),
+                    name
+                ),
+                pos,
+                LogCategory::Success,
+            ));
+        }
+    }
+}
@@ -7,7 +7,7 @@ use super::super::{
             item::{ItemInteract, ItemView},
             terrain::{TerrainInteract, TerrainView},
         },
-        Avatar, World,
+        World,
     },
     Action, ActionImpl,
     ActionPossibility::{self, No, Yes},
@@ -20,7 +20,8 @@ pub struct Drop {
 }
 
 impl ActionImpl for Drop {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
         if actor.wield.is_empty() {
             return No("You have nothing to drop".to_string());
         }
@@ -1,15 +1,31 @@
+pub use attach::Attach;
+pub use attack::Attack;
 pub use dig::Dig;
+pub use dismember::Dismember;
 pub use drop::Drop;
+pub use pick_up::PickUp;
+pub use put_in::PutIn;
 pub use raise::Raise;
 pub use read::Read;
 pub use skip::Skip;
+pub use take_off::TakeOff;
+pub use take_out::TakeOut;
 pub use walk::Walk;
+pub use wear::Wear;
 pub use wield::Wield;
 
+mod attach;
+mod attack;
 mod dig;
+mod dismember;
 mod drop;
+mod pick_up;
+mod put_in;
 mod raise;
 mod read;
 mod skip;
+mod take_off;
+mod take_out;
 mod walk;
+mod wear;
 mod wield;
@@ -0,0 +1,66 @@
+use geometry::Direction;
+
+use super::super::{
+    super::{
+        log::{LogCategory, LogEvent},
+        map::item::{ItemInteract, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+/// Put an item from the ground straight into a container
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct PickUp {
+    pub dir: Direction,
+}
+
+impl ActionImpl for PickUp {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        if actor.containers().next().is_none() {
+            return No("You have nothing to put items in".to_string());
+        }
+        let pos = actor.pos + self.dir;
+        let mut map = world.map();
+        if let Some(item) = map.get_tile(pos).items.last() {
+            if actor.container_for(item, None).is_some() {
+                Yes(item.wield_time(actor).round() as u32)
+            } else {
+                No(format!("There is no room for the {}", item.name()))
+            }
+        } else {
+            No("There is nothing to pick up".to_string())
+        }
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let pos = action.owner(world).pos + self.dir;
+        // the tile is not touched if there is nowhere to put the item
+        let container_id = world
+            .map()
+            .get_tile(pos)
+            .top_item()
+            .and_then(|item| action.owner(world).container_for(item, None));
+        if let Some(container_id) = container_id {
+            let item = world.map().get_tile_mut(pos).items.pop().unwrap();
+            let name = item.name();
+            action
+                .owner_mut(world)
+                .container_mut(container_id)
+                .unwrap()
+                .items
+                .push(item);
+            world.log().push(LogEvent::new(
+                format!(
+                    "{} picked up the {}",
+                    action.owner(world).name_for_actions(),
+                    name
+                ),
+                pos,
+                LogCategory::Success,
+            ));
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use super::super::{
+    super::{
+        log::{LogCategory, LogEvent},
+        map::item::{ItemInteract, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+/// Put a wielded item into a container
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct PutIn {
+    pub item_id: usize,
+}
+
+impl ActionImpl for PutIn {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        if let Some(item) = actor.wield.get(self.item_id) {
+            if actor.container_for(item, Some(self.item_id)).is_some() {
+                Yes(item.drop_time(actor).round() as u32)
+            } else {
+                No(format!("There is no room for the {}", item.name()))
+            }
+        } else {
+            No("You have nothing in your hands".to_string())
+        }
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let owner = action.owner_mut(world);
+        if self.item_id >= owner.wield.len() {
+            return;
+        }
+        let item = owner.wield.remove(self.item_id);
+        let name = item.name();
+        if let Some(container_id) = owner.container_for(&item, None) {
+            owner.container_mut(container_id).unwrap().items.push(item);
+            let owner = action.owner(world);
+            world.log().push(LogEvent::new(
+                format!("{} put away the {}", owner.name_for_actions(), name),
+                owner.pos,
+                LogCategory::Info,
+            ));
+        } else {
+            owner.wield.insert(self.item_id, item);
+        }
+    }
+}
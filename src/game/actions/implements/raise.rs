@@ -1,9 +1,12 @@
-use geometry::Direction;
+use geometry::{Direction, Point, TwoDimDirection};
 
 use super::super::{
     super::{
         log::{LogCategory, LogEvent},
-        map::item::{Item, ItemInteract},
+        map::{
+            item::{Item, ItemInteract},
+            items::Identity,
+        },
         Avatar, World,
     },
     Action, ActionImpl,
@@ -15,17 +18,34 @@ pub struct Raise {
     pub dir: Direction,
 }
 
-impl ActionImpl for Raise {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility {
-        let pos = actor.pos + self.dir;
-        if let Some(item) = world
+impl Raise {
+    /// Time to raise the corpse at `pos` and tiles its zombie will occupy,
+    /// new units look to the east
+    fn corpse_at(pos: Point, world: &World) -> Option<(u32, Vec<Point>)> {
+        world
             .map()
             .get_tile(pos)
             .items
             .iter()
-            .find(|i| matches!(i, Item::Corpse(..)))
-        {
-            return Yes(item.mass() / 10);
+            .find_map(|i| match i {
+                Item::Corpse(corpse) => Some((
+                    i.mass() / 10,
+                    Avatar::body_tiles(&corpse.body, pos, TwoDimDirection::East),
+                )),
+                _ => None,
+            })
+    }
+}
+
+impl ActionImpl for Raise {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let pos = world.get_unit(actor_id).pos + self.dir;
+        if let Some((length, tiles)) = Self::corpse_at(pos, world) {
+            return if let Some(obstacle) = world.tiles_obstacle(&tiles, None) {
+                No(obstacle)
+            } else {
+                Yes(length)
+            };
         }
 
         No("There is nothing to rise".to_string())
@@ -33,6 +53,11 @@ impl ActionImpl for Raise {
 
     fn on_finish(&self, action: &Action, world: &mut World) {
         let pos = action.owner(world).pos + self.dir;
+        // a segment can't stand up inside a wall or another unit
+        match Self::corpse_at(pos, world) {
+            Some((_, tiles)) if world.tiles_obstacle(&tiles, None).is_none() => {}
+            _ => return,
+        }
         let corpse_index = world
             .map()
             .get_tile(pos)
@@ -42,8 +67,16 @@ impl ActionImpl for Raise {
         if let Some(i) = corpse_index {
             let body = world.map().get_tile_mut(pos).items.remove(i);
             if let Item::Corpse(corpse) = body {
-                let name = corpse.character.age_name().to_owned();
-                let zombie = Avatar::zombie(corpse.character, corpse.body, pos);
+                let (zombie, name) = match corpse.character {
+                    Identity::Human(character) => {
+                        let name = character.age_name().to_owned();
+                        (Avatar::zombie(character, corpse.body, pos), name)
+                    }
+                    Identity::Animal(identity) => (
+                        Avatar::zombie_dog(identity, corpse.body, pos),
+                        "dog".to_string(),
+                    ),
+                };
                 world.add_unit(zombie);
                 world.log().push(LogEvent::new(
                     format!("Zombie {} stands up!", name),
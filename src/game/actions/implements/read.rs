@@ -3,7 +3,7 @@ use geometry::Direction;
 use super::super::{
     super::{
         log::{LogCategory, LogEvent},
-        Avatar, World,
+        World,
     },
     Action, ActionImpl,
     ActionPossibility::{self, No, Yes},
@@ -15,7 +15,8 @@ pub struct Read {
 }
 
 impl ActionImpl for Read {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
         let pos = actor.pos + self.dir;
         let mut map = world.map();
         // TODO: check skill of reading, and probably even another languages
@@ -1,14 +1,25 @@
 use super::super::{
-    super::{Avatar, World},
-    ActionImpl,
+    super::World,
+    Action, ActionImpl,
     ActionPossibility::{self, Yes},
 };
 
+/// Resting creature gets one point of stamina back in this count of ticks
+const REST_TICKS: u128 = 5;
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
 pub struct Skip {}
 
 impl ActionImpl for Skip {
-    fn is_possible(&self, _actor: &Avatar, _world: &World) -> ActionPossibility {
+    fn is_possible(&self, _actor_id: usize, _world: &World) -> ActionPossibility {
         Yes(1)
     }
+
+    fn stamina_cost(&self, _action: &Action, world: &World) -> i32 {
+        if world.meta.current_tick % REST_TICKS == 0 {
+            -1
+        } else {
+            0
+        }
+    }
 }
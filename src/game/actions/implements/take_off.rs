@@ -0,0 +1,75 @@
+use geometry::Direction;
+
+use super::super::{
+    super::{
+        bodies::Body,
+        log::{LogCategory, LogEvent},
+        map::item::{ItemInteract, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+/// Take off a worn item into hands, undress yourself or a zombie or a corpse nearby
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct TakeOff {
+    pub item_id: usize,
+    pub dir: Direction,
+}
+
+impl ActionImpl for TakeOff {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        let k = if let Some(k) = actor.body.capabilities().dress_k() {
+            k
+        } else {
+            return No("You have no hands to undress".to_string());
+        };
+        if actor.free_hands() == 0 {
+            return No("You have no free hands".to_string());
+        }
+        let k = if self.dir.is_here() { k } else { k * 1.5 };
+        let check = |body: Option<&Body>| {
+            let slot = body
+                .and_then(|body| body.wear.get(self.item_id))
+                .and_then(ItemInteract::wear_slot);
+            if let Some(slot) = slot {
+                Yes((slot.dress_time() as f32 * k / 2.0).round() as u32)
+            } else {
+                No("There is nothing to take off".to_string())
+            }
+        };
+        if self.dir.is_here() {
+            check(Some(&actor.body))
+        } else {
+            world.inspect_body_at(actor.pos + self.dir, check)
+        }
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let pos = action.owner(world).pos + self.dir;
+        let take = |body: Option<&mut Body>| {
+            body.filter(|body| self.item_id < body.wear.len())
+                .map(|body| body.wear.remove(self.item_id))
+        };
+        let item = if self.dir.is_here() {
+            take(Some(&mut action.owner_mut(world).body))
+        } else {
+            world.with_body_at(pos, take)
+        };
+        if let Some(item) = item {
+            let name = item.name();
+            action.owner_mut(world).wield.push(item);
+            world.log().push(LogEvent::new(
+                format!(
+                    "{} took off the {}",
+                    action.owner(world).name_for_actions(),
+                    name
+                ),
+                pos,
+                LogCategory::Info,
+            ));
+        }
+    }
+}
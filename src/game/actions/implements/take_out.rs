@@ -0,0 +1,55 @@
+use super::super::{
+    super::{
+        log::{LogCategory, LogEvent},
+        map::item::{ItemInteract, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+/// Take an item out of a container into hands
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct TakeOut {
+    pub container_id: usize,
+    pub item_id: usize,
+}
+
+impl ActionImpl for TakeOut {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        if actor.free_hands() == 0 {
+            return No("You have no free hands".to_string());
+        }
+        let item = actor
+            .containers()
+            .nth(self.container_id)
+            .and_then(|c| c.items.get(self.item_id));
+        if let Some(item) = item {
+            Yes(item.wield_time(actor).round() as u32)
+        } else {
+            No("There is no such item".to_string())
+        }
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let owner = action.owner_mut(world);
+        let item = owner.container_mut(self.container_id).and_then(|c| {
+            if self.item_id < c.items.len() {
+                Some(c.items.remove(self.item_id))
+            } else {
+                None
+            }
+        });
+        if let Some(item) = item {
+            let name = item.name();
+            owner.wield.push(item);
+            let owner = action.owner(world);
+            world.log().push(LogEvent::new(
+                format!("{} took out the {}", owner.name_for_actions(), name),
+                owner.pos,
+                LogCategory::Info,
+            ));
+        }
+    }
+}
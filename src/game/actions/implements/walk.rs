@@ -1,16 +1,18 @@
+use std::convert::TryFrom;
 use std::f32::consts::SQRT_2;
 
-use geometry::Direction;
+use geometry::{Direction, TwoDimDirection};
 
 use super::super::{
     super::{
         avatar::Soul,
         log::{LogCategory, LogEvent},
         map::{
+            item::ItemInteract,
             passage::Passage::Passable,
             terrain::{TerrainInteract, TerrainView},
         },
-        Avatar, World,
+        World,
     },
     Action, ActionImpl,
     ActionPossibility::{self, No, Yes},
@@ -22,22 +24,19 @@ pub struct Walk {
 }
 
 impl ActionImpl for Walk {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        if self.dir.is_here() {
+            return No("You are already here".to_string());
+        }
         let pos = actor.pos + self.dir;
+        let vision = TwoDimDirection::try_from(self.dir).unwrap_or(actor.vision);
+        if let Some(obstacle) = world.body_obstacle(actor_id, pos, vision) {
+            return No(obstacle);
+        }
         let mut map = world.map();
         let tile = map.get_tile(pos);
-        if !tile.terrain.is_passable() {
-            return No(format!("You can't walk to the {}", tile.terrain.name()));
-        }
-        let unit_on_tile = tile.units.iter().copied().next();
-        if let Some(unit_id) = unit_on_tile {
-            let unit = world.get_unit(unit_id);
-            return No(format!("{} is on the way", unit.name_for_actions()));
-        }
         Yes({
-            let person = match &actor.soul {
-                Soul::Zombie(person, ..) | Soul::Player(person) => person,
-            };
             let k_diagonal = match self.dir {
                 Direction::NorthEast
                 | Direction::SouthEast
@@ -47,15 +46,18 @@ impl ActionImpl for Walk {
             };
             let k_soul = match &actor.soul {
                 Soul::Zombie(..) => 1.5,
-                Soul::Player(..) => 1.0,
+                Soul::Player(..) | Soul::Villager(..) | Soul::Watchman(..) => 1.0,
+                Soul::Dog(..) => 0.5,
+                Soul::ZombieDog(..) => 0.75,
             };
-            let k_age = match person.appearance.age {
-                0 => 100.0,
-                1..=3 => 10.0,
-                4..=10 => 3.0,
-                11.. => 1.0,
+            let k_age = match actor.person().map(|p| p.appearance.age) {
+                Some(0) => 100.0,
+                Some(1..=3) => 10.0,
+                Some(4..=10) => 3.0,
+                Some(11..) | None => 1.0,
             };
-            let k = k_diagonal * k_soul * k_age;
+            let k_legs = actor.body.capabilities().walk_k();
+            let k = k_diagonal * k_soul * k_age * k_legs;
             if let Passable(pass_time) = tile.terrain.passage() {
                 f32::round(pass_time * k) as u32
             } else {
@@ -64,6 +66,11 @@ impl ActionImpl for Walk {
         })
     }
 
+    /// One point for a step and one more for every 10 kg carried
+    fn stamina_cost(&self, action: &Action, world: &World) -> i32 {
+        1 + (action.owner(world).carried_mass() / 10_000) as i32
+    }
+
     fn on_finish(&self, action: &Action, world: &mut World) {
         world.move_avatar(action.owner, self.dir);
         let pos = world.get_unit(action.owner).pos;
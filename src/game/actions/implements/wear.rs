@@ -0,0 +1,97 @@
+use geometry::Direction;
+
+use super::super::{
+    super::{
+        bodies::Body,
+        log::{LogCategory, LogEvent},
+        map::item::{ItemInteract, ItemView},
+        World,
+    },
+    Action, ActionImpl,
+    ActionPossibility::{self, No, Yes},
+};
+
+/// Put on a wielded item, dress yourself or a zombie or a corpse nearby
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct Wear {
+    pub item_id: usize,
+    pub dir: Direction,
+}
+
+impl ActionImpl for Wear {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        let item = if let Some(item) = actor.wield.get(self.item_id) {
+            item
+        } else {
+            return No("You have nothing in your hands".to_string());
+        };
+        let slot = if let Some(slot) = item.wear_slot() {
+            slot
+        } else {
+            return No(format!("You can't wear the {}", item.name()));
+        };
+        let k = if let Some(k) = actor.body.capabilities().dress_k() {
+            k
+        } else {
+            return No("You have no hands to dress".to_string());
+        };
+        let k = if self.dir.is_here() { k } else { k * 1.5 };
+        let length = (slot.dress_time() as f32 * k).round() as u32;
+        let check = |body: Option<&Body>| match body {
+            None => No("There is no one to dress".to_string()),
+            Some(body) if !body.has_slot(slot) => {
+                No(format!("There is no {} to wear it on", slot.name()))
+            }
+            Some(body) if body.worn_on(slot).is_some() => {
+                No(format!("There is already something on the {}", slot.name()))
+            }
+            Some(_) => Yes(length),
+        };
+        if self.dir.is_here() {
+            check(Some(&actor.body))
+        } else {
+            world.inspect_body_at(actor.pos + self.dir, check)
+        }
+    }
+
+    fn on_finish(&self, action: &Action, world: &mut World) {
+        let owner = action.owner_mut(world);
+        if self.item_id >= owner.wield.len() {
+            return;
+        }
+        let item = owner.wield.remove(self.item_id);
+        let name = item.name();
+        let pos = owner.pos + self.dir;
+        let item = if self.dir.is_here() {
+            owner.body.wear.push(item);
+            None
+        } else {
+            world.with_body_at(pos, |body| {
+                if let Some(body) = body {
+                    body.wear.push(item);
+                    None
+                } else {
+                    Some(item)
+                }
+            })
+        };
+        if let Some(item) = item {
+            action.owner_mut(world).wield.insert(self.item_id, item);
+            return;
+        }
+        let owner = action.owner(world);
+        let message = if self.dir.is_here() {
+            format!("{} put on the {}", owner.name_for_actions(), name)
+        } else {
+            format!(
+                "{} dressed the body in the {}",
+                owner.name_for_actions(),
+                name
+            )
+        };
+        world
+            .log()
+            .push(LogEvent::new(message, pos, LogCategory::Success));
+    }
+}
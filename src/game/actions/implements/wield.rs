@@ -4,7 +4,7 @@ use super::super::{
     super::{
         log::{LogCategory, LogEvent},
         map::item::{ItemInteract, ItemView},
-        Avatar, World,
+        World,
     },
     Action, ActionImpl,
     ActionPossibility::{self, No, Yes},
@@ -16,8 +16,13 @@ pub struct Wield {
 }
 
 impl ActionImpl for Wield {
-    fn is_possible(&self, actor: &Avatar, world: &World) -> ActionPossibility {
-        if !actor.wield.is_empty() {
+    fn is_possible(&self, actor_id: usize, world: &World) -> ActionPossibility {
+        let actor = world.get_unit(actor_id);
+        let hands = actor.body.capabilities().hands;
+        if hands == 0 {
+            return No("You have no hands to hold anything".to_string());
+        }
+        if actor.wield.len() >= hands as usize {
             return No("You already have something in your hands".to_string());
         }
         let pos = actor.pos + self.dir;
@@ -28,6 +33,17 @@ impl ActionImpl for Wield {
         }
     }
 
+    fn stamina_cost(&self, action: &Action, world: &World) -> i32 {
+        let pos = action.owner(world).pos + self.dir;
+        let mass = world
+            .map()
+            .get_tile(pos)
+            .items
+            .last()
+            .map_or(0, |i| i.mass());
+        1 + (mass / 10_000) as i32
+    }
+
     fn on_finish(&self, action: &Action, world: &mut World) {
         let pos = action.owner(world).pos + self.dir;
         let item = world.map().get_tile_mut(pos).items.pop();
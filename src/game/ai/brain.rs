@@ -1,8 +1,7 @@
-use super::super::ActionType;
+use super::super::{ActionType, World};
 
 pub trait Brain {
-    // TODO: Brain need to know who its owner is and check the world
     // TODO: async call plan() probably?
-    fn plan(&mut self);
+    fn plan(&mut self, unit_id: usize, world: &World);
     fn action(&self) -> Option<ActionType>;
 }
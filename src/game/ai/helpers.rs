@@ -0,0 +1,103 @@
+use geometry::{Direction, Point, DIR8};
+use rand::Rng;
+
+use crate::{fov::line_of_sight, pathfinding::find_path};
+
+use super::super::{
+    actions::{
+        implements::{Attack, Skip, Walk},
+        ActionType,
+    },
+    map::{terrain::TerrainInteract, WalkerMap},
+    Avatar, World,
+};
+
+/// How many tiles unit looks at while searching for a path
+const PATH_BUDGET: usize = 500;
+/// How far living creatures can see, walls and other opaque tiles block the view
+const VISION_RANGE: i32 = 16;
+
+pub fn wander(world: &World) -> ActionType {
+    Walk {
+        dir: match world.rng().gen_range(0..5) {
+            0 => Direction::East,
+            1 => Direction::West,
+            2 => Direction::North,
+            3 => Direction::South,
+            4 => Direction::Here,
+            _ => unreachable!(),
+        },
+    }
+    .into()
+}
+
+pub fn direction(from: Point, to: Point) -> Direction {
+    Direction::from_delta(to.x - from.x, to.y - from.y)
+}
+
+pub fn is_near(unit: &Avatar, pos: Point) -> bool {
+    unit.pos.square_distance(pos) <= 2
+}
+
+pub fn walk_to(unit_id: usize, pos: Point, world: &World) -> ActionType {
+    let unit = world.get_unit(unit_id);
+    let mut map = world.map();
+    let path = find_path(
+        &mut WalkerMap::new(&mut map, unit_id),
+        unit.pos,
+        pos,
+        PATH_BUDGET,
+    );
+    Walk {
+        dir: path
+            .first_step(unit.pos)
+            .unwrap_or_else(|| direction(unit.pos, pos)),
+    }
+    .into()
+}
+
+/// Attacks the target if it is near or comes closer to it
+pub fn attack(unit_id: usize, pos: Point, world: &World) -> ActionType {
+    let unit = world.get_unit(unit_id);
+    if is_near(unit, pos) {
+        Attack {
+            dir: direction(unit.pos, pos),
+        }
+        .into()
+    } else {
+        walk_to(unit_id, pos, world)
+    }
+}
+
+/// Step to the passable neighbour tile farthest from the danger
+pub fn flee(unit: &Avatar, from: Point, world: &World) -> ActionType {
+    let mut map = world.map();
+    DIR8.iter()
+        .copied()
+        .filter(|&d| {
+            let tile = map.get_tile(unit.pos + d);
+            tile.terrain.is_passable() && tile.units.is_empty()
+        })
+        .max_by_key(|&d| (unit.pos + d).square_distance(from))
+        .map_or_else(|| Skip {}.into(), |dir| Walk { dir }.into())
+}
+
+/// The position is close enough and nothing opaque is in the way
+pub fn can_see(unit: &Avatar, pos: Point, world: &World) -> bool {
+    let range = unit.body.capabilities().vision_range(VISION_RANGE) as u32;
+    unit.pos.square_distance(pos) <= range * range && line_of_sight(unit.pos, pos, &*world.map())
+}
+
+/// The closest living unit in sight that matches the filter
+pub fn closest_seen<F: Fn(usize, &Avatar) -> bool>(
+    unit_id: usize,
+    world: &World,
+    filter: F,
+) -> Option<usize> {
+    let unit = world.get_unit(unit_id);
+    world
+        .loaded_units()
+        .filter(|(i, u)| *i != unit_id && !u.dead && filter(*i, u) && can_see(unit, u.pos, world))
+        .min_by_key(|(_, u)| u.pos.square_distance(unit.pos))
+        .map(|(i, _)| i)
+}
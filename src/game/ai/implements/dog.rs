@@ -0,0 +1,72 @@
+use super::super::super::{
+    actions::{implements::Skip, ActionType},
+    log::{LogCategory, LogEvent},
+    World,
+};
+use super::super::{
+    helpers::{attack, can_see, closest_seen, wander},
+    Brain,
+};
+
+/// Dogs bark at strangers coming closer than this and bite if they don't go away
+const BARK_DISTANCE: u32 = 5;
+
+/// Stray dog, hates the dead and doesn't let strangers come close
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct DogAI {
+    action: ActionType,
+    #[serde(default)]
+    #[serde(rename = "a")]
+    angry: bool,
+}
+
+impl DogAI {
+    pub fn new() -> Self {
+        Self {
+            action: Skip {}.into(),
+            angry: false,
+        }
+    }
+}
+
+impl Default for DogAI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Brain for DogAI {
+    fn plan(&mut self, unit_id: usize, world: &World) {
+        let unit = world.get_unit(unit_id);
+        let zombie = closest_seen(unit_id, world, |_, u| u.is_zombie());
+        let player = world.player();
+        let stranger = !player.dead
+            && player.pos.square_distance(unit.pos) <= BARK_DISTANCE.pow(2)
+            && can_see(unit, player.pos, world);
+        self.action = if let Some(zombie) = zombie {
+            attack(unit_id, world.get_unit(zombie).pos, world)
+        } else if stranger && self.angry {
+            attack(unit_id, player.pos, world)
+        } else if stranger {
+            // first warning
+            self.angry = true;
+            world.log().push(LogEvent::new(
+                format!(
+                    "{} barks at {}",
+                    unit.name_for_actions(),
+                    player.name_as_object()
+                ),
+                unit.pos,
+                LogCategory::Warning,
+            ));
+            Skip {}.into()
+        } else {
+            self.angry = false;
+            wander(world)
+        };
+    }
+
+    fn action(&self) -> Option<ActionType> {
+        Some(self.action.clone())
+    }
+}
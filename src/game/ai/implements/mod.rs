@@ -1 +1,4 @@
+pub mod dog;
+pub mod villager;
+pub mod watchman;
 pub mod zombie;
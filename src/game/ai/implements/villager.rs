@@ -0,0 +1,49 @@
+use super::super::super::{
+    actions::{implements::Skip, ActionType},
+    World,
+};
+use super::super::{
+    helpers::{closest_seen, flee, wander},
+    Brain,
+};
+
+/// Villagers don't come closer to the necromancer than this
+const FEAR_DISTANCE: u32 = 5;
+
+/// Peaceful living human, runs away from the dead and keeps away from the necromancer
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct VillagerAI {
+    action: ActionType,
+}
+
+impl VillagerAI {
+    pub fn new() -> Self {
+        Self {
+            action: Skip {}.into(),
+        }
+    }
+}
+
+impl Default for VillagerAI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Brain for VillagerAI {
+    fn plan(&mut self, unit_id: usize, world: &World) {
+        let unit = world.get_unit(unit_id);
+        let danger = closest_seen(unit_id, world, |i, u| {
+            u.is_zombie() || (i == 0 && u.pos.square_distance(unit.pos) <= FEAR_DISTANCE.pow(2))
+        });
+        self.action = if let Some(danger) = danger {
+            flee(unit, world.get_unit(danger).pos, world)
+        } else {
+            wander(world)
+        };
+    }
+
+    fn action(&self) -> Option<ActionType> {
+        Some(self.action.clone())
+    }
+}
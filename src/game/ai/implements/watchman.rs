@@ -0,0 +1,105 @@
+use geometry::Point;
+
+use super::super::super::{
+    actions::{implements::Skip, ActionType},
+    avatar::Soul,
+    log::{LogCategory, LogEvent},
+    Avatar, World,
+};
+use super::super::{
+    helpers::{attack, can_see, closest_seen, walk_to, wander},
+    Brain,
+};
+
+/// How far from the post watchman goes while patrolling
+const PATROL_RADIUS: u32 = 8;
+/// Watchmen hear each other's alarm from this distance
+const ALARM_RADIUS: u32 = 24;
+/// How many ticks watchmen hunt the necromancer after seeing them at work
+const ALARM_DURATION: u128 = 3_000;
+
+/// Guards the graveyard, hunts the dead and raises an alarm on seeing someone digging graves
+/// or raising the dead
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct WatchmanAI {
+    action: ActionType,
+    #[serde(rename = "p")]
+    pub post: Point,
+    /// Tick when the alarm is over, None if there is no alarm
+    #[serde(default)]
+    #[serde(rename = "t")]
+    pub alarm: Option<u128>,
+}
+
+impl WatchmanAI {
+    pub fn new(post: Point) -> Self {
+        Self {
+            action: Skip {}.into(),
+            post,
+            alarm: None,
+        }
+    }
+
+    /// Zombies are always hunted, the necromancer only after being caught
+    fn is_suspect(&self, unit_id: usize, unit: &Avatar) -> bool {
+        unit.is_zombie() || (unit_id == 0 && (self.alarm.is_some() || Self::is_robbing(unit)))
+    }
+
+    fn is_robbing(unit: &Avatar) -> bool {
+        unit.action.as_ref().map_or(false, |a| {
+            matches!(a.typ, ActionType::Dig(..) | ActionType::Raise(..))
+        })
+    }
+
+    /// The latest alarm of watchmen around, hearing it doesn't make it longer
+    fn heard_alarm(unit: &Avatar, world: &World) -> Option<u128> {
+        world
+            .loaded_units()
+            .filter(|(_, u)| !u.dead && u.pos.square_distance(unit.pos) <= ALARM_RADIUS.pow(2))
+            .filter_map(|(_, u)| match &u.soul {
+                Soul::Watchman(_, brain) => brain.alarm,
+                _ => None,
+            })
+            .max()
+    }
+}
+
+impl Default for WatchmanAI {
+    fn default() -> Self {
+        Self::new(Point::new(0, 0))
+    }
+}
+
+impl Brain for WatchmanAI {
+    fn plan(&mut self, unit_id: usize, world: &World) {
+        let unit = world.get_unit(unit_id);
+        let tick = world.meta.current_tick;
+        self.alarm = self
+            .alarm
+            .max(Self::heard_alarm(unit, world))
+            .filter(|&until| until > tick);
+        let player = world.player();
+        if !player.dead && Self::is_robbing(player) && can_see(unit, player.pos, world) {
+            if self.alarm.is_none() {
+                world.log().push(LogEvent::new(
+                    format!("{} shouts: \"Grave robbers!\"", unit.name_for_actions()),
+                    unit.pos,
+                    LogCategory::Danger,
+                ));
+            }
+            self.alarm = Some(tick + ALARM_DURATION);
+        }
+        let suspect = closest_seen(unit_id, world, |i, u| self.is_suspect(i, u));
+        self.action = if let Some(suspect) = suspect {
+            attack(unit_id, world.get_unit(suspect).pos, world)
+        } else if unit.pos.square_distance(self.post) > PATROL_RADIUS.pow(2) {
+            walk_to(unit_id, self.post, world)
+        } else {
+            wander(world)
+        };
+    }
+
+    fn action(&self) -> Option<ActionType> {
+        Some(self.action.clone())
+    }
+}
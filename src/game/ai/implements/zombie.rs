@@ -1,21 +1,100 @@
-use geometry::Direction;
-use rand::{thread_rng, Rng};
+use geometry::Point;
 
-use super::super::super::actions::{
-    implements::{Skip, Walk},
-    ActionType,
+use super::super::super::{
+    actions::{
+        implements::{Dig, Drop, Skip, Wield},
+        ActionImpl, ActionPossibility, ActionType,
+    },
+    log::{LogCategory, LogEvent},
+    map::terrain::TerrainInteract,
+    Avatar, World,
 };
-use super::super::Brain;
+use super::super::{
+    helpers::{attack, direction, is_near, walk_to, wander},
+    Brain, Order,
+};
+
+/// How far from the guarded point zombie can go
+const GUARD_RADIUS: u32 = 5;
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct ZombieAI {
     action: ActionType,
+    #[serde(default)]
+    #[serde(rename = "o")]
+    pub order: Order,
 }
 
 impl ZombieAI {
     pub fn new() -> Self {
         Self {
             action: Skip {}.into(),
+            order: Order::default(),
+        }
+    }
+
+    fn is_intruder(unit_id: usize, unit: &Avatar) -> bool {
+        unit_id != 0 && !unit.dead && !unit.is_zombie()
+    }
+
+    fn dig(unit_id: usize, pos: Point, world: &World) -> Option<ActionType> {
+        let unit = world.get_unit(unit_id);
+        if !world.map().get_tile(pos).terrain.is_diggable() {
+            None
+        } else if is_near(unit, pos) {
+            Some(
+                Dig {
+                    dir: direction(unit.pos, pos),
+                }
+                .into(),
+            )
+        } else {
+            Some(walk_to(unit_id, pos, world))
+        }
+    }
+
+    fn carry(unit_id: usize, from: Point, to: Point, world: &World) -> Option<ActionType> {
+        let unit = world.get_unit(unit_id);
+        if !unit.wield.is_empty() {
+            Some(if is_near(unit, to) {
+                Drop {
+                    item_id: 0,
+                    dir: direction(unit.pos, to),
+                }
+                .into()
+            } else {
+                walk_to(unit_id, to, world)
+            })
+        } else if world.map().get_tile(from).items.is_empty() {
+            None
+        } else if is_near(unit, from) {
+            Some(
+                Wield {
+                    dir: direction(unit.pos, from),
+                }
+                .into(),
+            )
+        } else {
+            Some(walk_to(unit_id, from, world))
+        }
+    }
+
+    fn guard(unit_id: usize, center: Point, world: &World) -> ActionType {
+        let unit = world.get_unit(unit_id);
+        let intruder = world
+            .loaded_units()
+            .filter(|(i, u)| {
+                Self::is_intruder(*i, u) && u.pos.square_distance(center) <= GUARD_RADIUS.pow(2)
+            })
+            .min_by_key(|(_, u)| u.pos.square_distance(unit.pos))
+            .map(|(i, _)| i);
+        if let Some(intruder) = intruder {
+            return attack(unit_id, world.get_unit(intruder).pos, world);
+        }
+        if unit.pos.square_distance(center) > GUARD_RADIUS.pow(2) {
+            walk_to(unit_id, center, world)
+        } else {
+            wander(world)
         }
     }
 }
@@ -27,24 +106,55 @@ impl Default for ZombieAI {
 }
 
 impl Brain for ZombieAI {
-    fn plan(&mut self) {
-        // TODO: use world.rng
-        let mut rng = thread_rng();
-
-        self.action = Walk {
-            dir: match rng.gen_range(0..5) {
-                0 => Direction::East,
-                1 => Direction::West,
-                2 => Direction::North,
-                3 => Direction::South,
-                4 => Direction::Here,
-                _ => unreachable!(),
-            },
+    fn plan(&mut self, unit_id: usize, world: &World) {
+        let unit = world.get_unit(unit_id);
+        // only zombies with brain can understand orders
+        let order = if unit.body.capabilities().brain {
+            self.order
+        } else {
+            Order::Wander
+        };
+        let action = match order {
+            Order::Wander => Some(wander(world)),
+            Order::Stay => None,
+            Order::FollowMe => {
+                let pos = world.player().pos;
+                (!is_near(unit, pos)).then(|| walk_to(unit_id, pos, world))
+            }
+            Order::GoTo(pos) => (unit.pos != pos).then(|| walk_to(unit_id, pos, world)),
+            Order::Dig(pos) => Self::dig(unit_id, pos, world),
+            Order::Carry(from, to) => Self::carry(unit_id, from, to, world),
+            Order::Guard(center) => Some(Self::guard(unit_id, center, world)),
+        };
+        // impossible job would be tried again every tick, better to wait for a new order
+        if let (Order::GoTo(..) | Order::Dig(..) | Order::Carry(..), Some(action)) =
+            (order, &action)
+        {
+            if let ActionPossibility::No(reason) = action.is_possible(unit_id, world) {
+                world.log().push(LogEvent::new(
+                    format!(
+                        "{} can't do what you ordered: {}",
+                        unit.name_for_actions(),
+                        reason
+                    ),
+                    unit.pos,
+                    LogCategory::Warning,
+                ));
+                self.order = Order::Stay;
+                self.action = Skip {}.into();
+                return;
+            }
         }
-        .into();
+        self.action = action.unwrap_or_else(|| {
+            // job is done, wait for the next order
+            if order != Order::FollowMe {
+                self.order = Order::Stay;
+            }
+            Skip {}.into()
+        });
     }
 
     fn action(&self) -> Option<ActionType> {
-        Some(self.action)
+        Some(self.action.clone())
     }
 }
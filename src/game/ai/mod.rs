@@ -1,5 +1,8 @@
 pub use brain::Brain;
-pub use implements::zombie::ZombieAI;
+pub use implements::{dog::DogAI, villager::VillagerAI, watchman::WatchmanAI, zombie::ZombieAI};
+pub use order::Order;
 
 mod brain;
+mod helpers;
 mod implements;
+mod order;
@@ -0,0 +1,69 @@
+use geometry::Point;
+
+/// Command given to a raised zombie by its master
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Order {
+    #[default]
+    #[serde(rename = "w")]
+    Wander,
+    #[serde(rename = "s")]
+    Stay,
+    #[serde(rename = "f")]
+    FollowMe,
+    #[serde(rename = "g")]
+    GoTo(Point),
+    #[serde(rename = "d")]
+    Dig(Point),
+    /// Carry items one by one from the first tile to the second
+    #[serde(rename = "c")]
+    Carry(Point, Point),
+    /// Stay around the point and attack intruders
+    #[serde(rename = "a")]
+    Guard(Point),
+}
+
+impl Order {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Order::Wander => "Wander around",
+            Order::Stay => "Stay here",
+            Order::FollowMe => "Follow me",
+            Order::GoTo(..) => "Go to...",
+            Order::Dig(..) => "Dig...",
+            Order::Carry(..) => "Carry items from... to...",
+            Order::Guard(..) => "Guard area around...",
+        }
+    }
+
+    /// How many tiles player should choose to give this order
+    pub fn targets_needed(&self) -> usize {
+        match self {
+            Order::Wander | Order::Stay | Order::FollowMe => 0,
+            Order::GoTo(..) | Order::Dig(..) | Order::Guard(..) => 1,
+            Order::Carry(..) => 2,
+        }
+    }
+
+    pub fn with_targets(self, targets: &[Point]) -> Self {
+        match self {
+            Order::GoTo(..) => Order::GoTo(targets[0]),
+            Order::Dig(..) => Order::Dig(targets[0]),
+            Order::Carry(..) => Order::Carry(targets[0], targets[1]),
+            Order::Guard(..) => Order::Guard(targets[0]),
+            order => order,
+        }
+    }
+
+    pub fn all() -> [Order; 7] {
+        let pos = Point::default();
+        [
+            Order::FollowMe,
+            Order::Stay,
+            Order::Wander,
+            Order::GoTo(pos),
+            Order::Dig(pos),
+            Order::Carry(pos, pos),
+            Order::Guard(pos),
+        ]
+    }
+}
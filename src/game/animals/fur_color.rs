@@ -1,6 +1,9 @@
 use rand::distributions::{Distribution, Standard};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use tetra::graphics::Color;
+
+use crate::colors::Colors;
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
 pub enum FurColor {
@@ -22,6 +25,21 @@ pub enum FurColor {
     Black,
 }
 
+impl From<FurColor> for Color {
+    fn from(c: FurColor) -> Self {
+        match c {
+            FurColor::White => Colors::WHITE_SMOKE,
+            FurColor::Gray => Colors::GRAY,
+            FurColor::Yellow => Colors::BURLY_WOOD,
+            FurColor::Ginger => Colors::PERU,
+            FurColor::LightBrown => Colors::SIENNA,
+            FurColor::MediumBrown => Colors::SADDLE_BROWN,
+            FurColor::DarkBrown => Colors::DARK_SEPIA,
+            FurColor::Black => Colors::DARKEST_GRAY,
+        }
+    }
+}
+
 impl Distribution<FurColor> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FurColor {
         match rng.gen_range(0..8) {
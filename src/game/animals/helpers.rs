@@ -6,14 +6,14 @@ use geometry::Point;
 
 use super::{
     super::{
-        bodies::{Body, BodySize, Freshness, OrganData, Sex},
+        bodies::{Body, Freshness, OrganData},
         map::items::{BodyPart, BodyPartType},
     },
-    FurColor,
+    AnimalIdentity, FurColor,
 };
 
-pub fn dog_brain(organ_data: OrganData) -> BodyPart {
-    BodyPart::new("brain", BodyPartType::DogBrain(organ_data))
+pub fn dog_brain(organ_data: OrganData, identity: AnimalIdentity) -> BodyPart {
+    BodyPart::new("brain", BodyPartType::DogBrain(organ_data, identity))
 }
 
 pub fn dog_nose(organ_data: OrganData, fur_color: FurColor) -> BodyPart {
@@ -38,10 +38,12 @@ pub fn dog_ear(organ_data: OrganData, fur_color: FurColor, left: bool) -> BodyPa
     )
 }
 
-pub fn dog_head(organ_data: OrganData, fur_color: FurColor, sex: Sex) -> BodyPart {
+pub fn dog_head(identity: &AnimalIdentity, freshness: Freshness) -> BodyPart {
+    let organ_data = OrganData::animal(identity, freshness);
+    let fur_color = identity.fur_color;
     BodyPart::new(
         "head",
-        BodyPartType::DogHead(organ_data.clone(), fur_color, sex),
+        BodyPartType::DogHead(organ_data.clone(), fur_color, identity.sex),
     )
     .with_outside(vec![
         dog_eye(organ_data.clone(), true),
@@ -51,7 +53,7 @@ pub fn dog_head(organ_data: OrganData, fur_color: FurColor, sex: Sex) -> BodyPar
         dog_nose(organ_data.clone(), fur_color),
         dog_maw(organ_data.clone(), fur_color),
     ])
-    .with_inside(vec![dog_brain(organ_data)])
+    .with_inside(vec![dog_brain(organ_data, identity.clone())])
 }
 
 pub fn dog_paw(organ_data: OrganData, fur_color: FurColor, front: bool, left: bool) -> BodyPart {
@@ -100,45 +102,38 @@ pub fn dog_kidney(organ_data: OrganData, left: bool) -> BodyPart {
     )
 }
 
-pub fn dog_torso(organ_data: OrganData, fur_color: FurColor, sex: Sex) -> BodyPart {
+pub fn dog_torso(identity: &AnimalIdentity, freshness: Freshness) -> BodyPart {
+    let organ_data = OrganData::animal(identity, freshness);
+    let fur_color = identity.fur_color;
     BodyPart::new(
         "torso",
-        BodyPartType::DogTorso(organ_data.clone(), fur_color, sex),
+        BodyPartType::DogTorso(organ_data.clone(), fur_color, identity.sex),
     )
     .with_outside(vec![
-        dog_head(organ_data.clone(), fur_color, sex),
+        dog_head(identity, freshness),
         dog_paw(organ_data.clone(), fur_color, true, true),
         dog_paw(organ_data.clone(), fur_color, true, false),
         dog_paw(organ_data.clone(), fur_color, false, true),
         dog_paw(organ_data.clone(), fur_color, false, false),
         dog_tail(organ_data.clone(), fur_color),
     ])
-    .with_inside(vec![
-        dog_heart(organ_data.clone()),
-        dog_lung(organ_data.clone(), true),
-        dog_lung(organ_data.clone(), false),
-        dog_stomach(organ_data.clone()),
-        dog_kidney(organ_data.clone(), true),
-        dog_kidney(organ_data.clone(), false),
-        dog_liver(organ_data.clone()),
-        dog_intestines(organ_data),
-    ])
-}
-
-pub fn dog_body(
-    freshness: Freshness,
-    age: u8,
-    size: BodySize,
-    fur_color: FurColor,
-    sex: Sex,
-) -> Body {
-    let organ_data = OrganData {
-        freshness,
-        age,
-        size,
-        alive: true,
-    };
-    let parts = HashMap::from([(Point::new(0, 0), dog_torso(organ_data, fur_color, sex))]);
+    .with_inside(match freshness {
+        Freshness::Fresh | Freshness::Rotten => vec![
+            dog_heart(organ_data.clone()),
+            dog_lung(organ_data.clone(), true),
+            dog_lung(organ_data.clone(), false),
+            dog_stomach(organ_data.clone()),
+            dog_kidney(organ_data.clone(), true),
+            dog_kidney(organ_data.clone(), false),
+            dog_liver(organ_data.clone()),
+            dog_intestines(organ_data),
+        ],
+        Freshness::Skeletal => vec![],
+    })
+}
+
+pub fn dog_body(identity: &AnimalIdentity, freshness: Freshness) -> Body {
+    let parts = HashMap::from([(Point::new(0, 0), dog_torso(identity, freshness))]);
     Body::new(parts)
 }
 
@@ -152,18 +147,13 @@ mod tests {
             map::items::BodyPartType,
             ItemView,
         },
-        dog_body, FurColor,
+        dog_body, AnimalIdentity, FurColor,
     };
 
     #[test]
     fn test_dog_body() {
-        let body = dog_body(
-            Freshness::Fresh,
-            3,
-            BodySize::Normal,
-            FurColor::Black,
-            Sex::Male,
-        );
+        let identity = AnimalIdentity::new(3, Sex::Male, BodySize::Normal, FurColor::Black, true);
+        let body = dog_body(&identity, Freshness::Fresh);
         let torso = body.parts.get(&Point::new(0, 0)).unwrap();
         assert_eq!("fresh dog torso", torso.name());
         assert!(matches!(
@@ -174,6 +164,8 @@ mod tests {
                     age: 3,
                     size: BodySize::Normal,
                     alive: true,
+                    decay: 0,
+                    wounds: 0,
                 },
                 FurColor::Black,
                 Sex::Male,
@@ -206,12 +198,21 @@ mod tests {
         assert_eq!("fresh dog brain", brain.name());
         assert!(matches!(
             brain.typ,
-            BodyPartType::DogBrain(OrganData {
-                freshness: Freshness::Fresh,
-                age: 3,
-                size: BodySize::Normal,
-                alive: true,
-            })
+            BodyPartType::DogBrain(
+                OrganData {
+                    freshness: Freshness::Fresh,
+                    age: 3,
+                    size: BodySize::Normal,
+                    alive: true,
+                    decay: 0,
+                    wounds: 0,
+                },
+                AnimalIdentity {
+                    name: None,
+                    sex: Sex::Male,
+                    ..
+                }
+            )
         ))
     }
 }
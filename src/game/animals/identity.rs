@@ -0,0 +1,53 @@
+use rand::distributions::Standard;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    super::bodies::{BodySize, Sex},
+    FurColor,
+};
+
+/// Who the animal is, it's stored in the brain like `Personality` for humans
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AnimalIdentity {
+    /// Only pets have names, strays don't
+    #[serde(rename = "n")]
+    pub name: Option<String>,
+    #[serde(rename = "a")]
+    pub age: u8,
+    #[serde(rename = "s")]
+    pub sex: Sex,
+    #[serde(rename = "z")]
+    pub body_size: BodySize,
+    #[serde(rename = "f")]
+    pub fur_color: FurColor,
+    #[serde(rename = "l")]
+    pub alive: bool,
+}
+
+impl AnimalIdentity {
+    pub fn new(age: u8, sex: Sex, body_size: BodySize, fur_color: FurColor, alive: bool) -> Self {
+        Self {
+            name: None,
+            age,
+            sex,
+            body_size,
+            fur_color,
+            alive,
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(rng: &mut R, alive: bool) -> Self {
+        Self::new(
+            rng.gen_range(1..10),
+            if rng.gen_bool(0.5) {
+                Sex::Male
+            } else {
+                Sex::Female
+            },
+            rng.sample(Standard),
+            rng.sample(Standard),
+            alive,
+        )
+    }
+}
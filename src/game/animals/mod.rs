@@ -1,4 +1,6 @@
 pub use self::fur_color::FurColor;
+pub use self::identity::AnimalIdentity;
 
 mod fur_color;
 pub mod helpers;
+mod identity;
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use std::collections::VecDeque;
+
 use geometry::{Point, TwoDimDirection, Vec2};
 // TODO: remove this
 use tetra::{graphics::DrawParams, Context};
@@ -7,17 +9,29 @@ use tetra::{graphics::DrawParams, Context};
 use crate::{assets::Tileset, colors::Colors};
 
 use super::{
-    ai::ZombieAI,
+    ai::{DogAI, Order, VillagerAI, WatchmanAI, ZombieAI},
+    animals::AnimalIdentity,
     bodies::{Body, Freshness, OrganData},
     human::{helpers::human_body, Gender, Personality},
-    map::items::{BodyPartType, Cloak, Hat},
-    Action, Item, ItemView,
+    map::{
+        item::ItemInteract,
+        items::{Axe, BodyPart, BodyPartType, Cloak, Container, Hat},
+    },
+    Action, ActionType, Item, ItemView,
 };
 
+pub const MAX_STAMINA: u8 = 100;
+/// Ticks of extra decay for every point of stamina zombie spends
+const OVERWORK_DECAY: u32 = 600;
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub enum Soul {
     Player(Personality),
     Zombie(Personality, ZombieAI),
+    Villager(Personality, VillagerAI),
+    Watchman(Personality, WatchmanAI),
+    Dog(AnimalIdentity, DogAI),
+    ZombieDog(AnimalIdentity, ZombieAI),
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -25,10 +39,13 @@ pub struct Avatar {
     pub body: Body,
     pub pos: Point,
     pub action: Option<Action>,
-    pub vision: TwoDimDirection, // TODO: rotation of multitile body
-    pub wield: Vec<Item>,        // TODO: custom struct with hands counter
+    /// Actions to start one by one after the current one is finished
+    pub action_queue: VecDeque<ActionType>,
+    pub vision: TwoDimDirection, // multitile bodies are turned this way
+    pub wield: Vec<Item>,        // count of hands is limited by the body capabilities
     pub stamina: u8,
     pub soul: Soul,
+    pub dead: bool,
     // TODO: traits
     // TODO: skills
 }
@@ -45,21 +62,184 @@ impl Avatar {
         Self::new(body, Soul::Zombie(personality, ZombieAI::default()), pos)
     }
 
+    pub fn villager(personality: Personality, pos: Point) -> Self {
+        let mut body = human_body(&personality, Freshness::Fresh);
+        body.wear.push(Cloak::new().into());
+        Self::new(body, Soul::Villager(personality, VillagerAI::new()), pos)
+    }
+
+    /// Watchman patrols around the post
+    pub fn watchman(personality: Personality, post: Point) -> Self {
+        let mut body = human_body(&personality, Freshness::Fresh);
+        body.wear.push(Cloak::new().into());
+        body.wear.push(Hat::new().into());
+        let mut avatar = Self::new(
+            body,
+            Soul::Watchman(personality, WatchmanAI::new(post)),
+            post,
+        );
+        avatar.wield.push(Axe::new().into());
+        avatar
+    }
+
+    pub fn dog(identity: AnimalIdentity, body: Body, pos: Point) -> Self {
+        Self::new(body, Soul::Dog(identity, DogAI::new()), pos)
+    }
+
+    pub fn zombie_dog(identity: AnimalIdentity, body: Body, pos: Point) -> Self {
+        Self::new(body, Soul::ZombieDog(identity, ZombieAI::default()), pos)
+    }
+
     pub fn new(body: Body, soul: Soul, pos: Point) -> Self {
         Avatar {
             body,
             soul,
             pos,
             action: None,
+            action_queue: VecDeque::new(),
             vision: TwoDimDirection::East,
             wield: Vec::new(),
-            stamina: 100,
+            stamina: MAX_STAMINA,
+            dead: false,
+        }
+    }
+
+    /// Animals have no personality
+    pub fn person(&self) -> Option<&Personality> {
+        match &self.soul {
+            Soul::Player(p)
+            | Soul::Zombie(p, ..)
+            | Soul::Villager(p, ..)
+            | Soul::Watchman(p, ..) => Some(p),
+            Soul::Dog(..) | Soul::ZombieDog(..) => None,
         }
     }
 
-    pub fn person(&self) -> &Personality {
+    /// Humans have no animal identity
+    pub fn animal(&self) -> Option<&AnimalIdentity> {
         match &self.soul {
-            Soul::Player(p) | Soul::Zombie(p, ..) => p,
+            Soul::Dog(identity, ..) | Soul::ZombieDog(identity, ..) => Some(identity),
+            Soul::Player(..) | Soul::Zombie(..) | Soul::Villager(..) | Soul::Watchman(..) => None,
+        }
+    }
+
+    pub fn is_zombie(&self) -> bool {
+        matches!(self.soul, Soul::Zombie(..) | Soul::ZombieDog(..))
+    }
+
+    /// Map position of the body part placed at `offset` in the body,
+    /// body lies along the vision direction with (0, 0) in front
+    pub fn segment_pos(&self, offset: Point) -> Point {
+        Self::rotate(self.pos, offset, self.vision)
+    }
+
+    /// Every tile occupied by the body, the unit position goes first
+    pub fn occupied_tiles(&self) -> Vec<Point> {
+        Self::body_tiles(&self.body, self.pos, self.vision)
+    }
+
+    /// Tiles the body would occupy standing at `pos` and facing `vision`
+    pub fn body_tiles(body: &Body, pos: Point, vision: TwoDimDirection) -> Vec<Point> {
+        let mut offsets: Vec<&Point> = body.parts.keys().collect();
+        offsets.sort_by_key(|p| (p.y, p.x));
+        let mut tiles = vec![pos];
+        for offset in offsets {
+            let tile = Self::rotate(pos, *offset, vision);
+            if !tiles.contains(&tile) {
+                tiles.push(tile);
+            }
+        }
+        tiles
+    }
+
+    /// The unit looks from its head
+    pub fn head_pos(&self) -> Point {
+        self.segment_pos(self.body.head_root())
+    }
+
+    /// Body's front (negative y) is turned to the vision direction
+    fn rotate(pos: Point, offset: Point, vision: TwoDimDirection) -> Point {
+        if matches!(vision, TwoDimDirection::East) {
+            pos + (-offset.y, offset.x)
+        } else {
+            pos + (offset.y, -offset.x)
+        }
+    }
+
+    pub fn free_hands(&self) -> usize {
+        (self.body.capabilities().hands as usize).saturating_sub(self.wield.len())
+    }
+
+    /// Wielded and worn items that can hold other items, wielded ones go first
+    pub fn containers(&self) -> impl Iterator<Item = &Container> {
+        self.wield
+            .iter()
+            .chain(self.body.wear.iter())
+            .filter_map(ItemInteract::container)
+    }
+
+    pub fn container_mut(&mut self, container_id: usize) -> Option<&mut Container> {
+        self.wield
+            .iter_mut()
+            .chain(self.body.wear.iter_mut())
+            .filter_map(ItemInteract::container_mut)
+            .nth(container_id)
+    }
+
+    /// First container with enough room for the item,
+    /// `except` is a wielded item that should be skipped (when it is the item itself)
+    pub fn container_for(&self, item: &Item, except: Option<usize>) -> Option<usize> {
+        self.wield
+            .iter()
+            .enumerate()
+            .map(|(i, other)| (Some(i), other))
+            .chain(self.body.wear.iter().map(|other| (None, other)))
+            .filter_map(|(i, other)| other.container().map(|c| (i, c)))
+            .position(|(i, c)| (except.is_none() || i != except) && c.fits(item))
+    }
+
+    /// Mass of everything in hands and worn, with contents of containers
+    pub fn carried_mass(&self) -> u32 {
+        self.wield
+            .iter()
+            .chain(self.body.wear.iter())
+            .map(ItemInteract::mass)
+            .sum()
+    }
+
+    /// Zombies never get tired, but overworking rots them faster, light work doesn't
+    pub fn spend_stamina(&mut self, cost: i32) {
+        match self.soul {
+            Soul::Zombie(..) | Soul::ZombieDog(..) => {
+                if cost > 1 {
+                    self.body.decay((cost - 1) as u32 * OVERWORK_DECAY);
+                }
+            }
+            Soul::Player(..) | Soul::Villager(..) | Soul::Watchman(..) | Soul::Dog(..) => {
+                self.stamina =
+                    (i32::from(self.stamina) - cost).clamp(0, i32::from(MAX_STAMINA)) as u8;
+            }
+        }
+    }
+
+    /// Multiplier for the length of actions, tired creatures are slow
+    pub fn fatigue_k(&self) -> f32 {
+        let half = MAX_STAMINA / 2;
+        if self.stamina >= half {
+            1.0
+        } else {
+            2.0 - f32::from(self.stamina) / f32::from(half)
+        }
+    }
+
+    /// Returns false if the unit doesn't obey orders
+    pub fn give_order(&mut self, order: Order) -> bool {
+        match &mut self.soul {
+            Soul::Zombie(_, brain) | Soul::ZombieDog(_, brain) => {
+                brain.order = order;
+                true
+            }
+            Soul::Player(..) | Soul::Villager(..) | Soul::Watchman(..) | Soul::Dog(..) => false,
         }
     }
 
@@ -67,6 +247,36 @@ impl Avatar {
         match &self.soul {
             Soul::Player(..) => "You".to_string(),
             Soul::Zombie(person, ..) => format!("Zombie {}", person.mind.name),
+            Soul::Villager(person, ..) => person.mind.name.clone(),
+            Soul::Watchman(person, ..) => format!("Watchman {}", person.mind.name),
+            Soul::Dog(identity, ..) => identity
+                .name
+                .clone()
+                .unwrap_or_else(|| "The stray dog".to_string()),
+            Soul::ZombieDog(identity, ..) => {
+                format!("Zombie {}", identity.name.as_deref().unwrap_or("dog"))
+            }
+        }
+    }
+
+    /// "you" or "Zombie Name"
+    pub fn name_as_object(&self) -> String {
+        match &self.soul {
+            Soul::Player(..) => "you".to_string(),
+            Soul::Dog(AnimalIdentity { name: None, .. }, ..) => "the stray dog".to_string(),
+            Soul::Zombie(..)
+            | Soul::Villager(..)
+            | Soul::Watchman(..)
+            | Soul::Dog(..)
+            | Soul::ZombieDog(..) => self.name_for_actions(),
+        }
+    }
+
+    /// "Your" or "Zombie Name's"
+    pub fn name_possessive(&self) -> String {
+        match &self.soul {
+            Soul::Player(..) => "Your".to_string(),
+            _ => format!("{}'s", self.name_for_actions()),
         }
     }
 
@@ -75,10 +285,36 @@ impl Avatar {
         &self,
         ctx: &mut Context,
         tileset: &Tileset,
-        mut position: Vec2,
+        position: Vec2,
         zoom: f32,
         rotate: bool,
     ) {
+        let (position, scale) = self.sprite_transform(position, zoom, rotate);
+        self.draw_body(ctx, tileset, position, scale);
+        if let Some(item) = self.wield.get(0) {
+            let offset = if !rotate || matches!(self.vision, TwoDimDirection::East) {
+                Vec2::new(15.0 * zoom, 10.0 * zoom)
+            } else {
+                Vec2::new(-15.0 * zoom, 10.0 * zoom)
+            };
+            tileset.draw_region(
+                ctx,
+                item.looks_like(),
+                DrawParams::new()
+                    .position(position + offset)
+                    .scale(scale * -1.0),
+            );
+        }
+    }
+
+    /// Draws one of the rear tiles of a multitile body, without wielded items
+    pub fn draw_segment(&self, ctx: &mut Context, tileset: &Tileset, position: Vec2, zoom: f32) {
+        let (position, scale) = self.sprite_transform(position, zoom, true);
+        self.draw_body(ctx, tileset, position, scale);
+    }
+
+    /// Sprites are mirrored when the unit looks to the west
+    fn sprite_transform(&self, mut position: Vec2, zoom: f32, rotate: bool) -> (Vec2, Vec2) {
         // TODO: create canvas
         let scale = if !rotate || matches!(self.vision, TwoDimDirection::East) {
             Vec2::new(zoom, zoom)
@@ -86,6 +322,10 @@ impl Avatar {
             position.x += 10.0 * zoom;
             Vec2::new(-zoom, zoom)
         };
+        (position, scale)
+    }
+
+    fn draw_body(&self, ctx: &mut Context, tileset: &Tileset, position: Vec2, scale: Vec2) {
         match &self.soul {
             Soul::Zombie(person, ..) => {
                 let freshness =
@@ -135,7 +375,7 @@ impl Avatar {
                         .color(color),
                 );
             }
-            Soul::Player(person) => {
+            Soul::Player(person) | Soul::Villager(person, ..) | Soul::Watchman(person, ..) => {
                 // TODO: draw wear
                 tileset.draw_region(
                     ctx,
@@ -150,20 +390,36 @@ impl Avatar {
                         .color(person.appearance.skin_tone.into()),
                 );
             }
-        }
-        if let Some(item) = self.wield.get(0) {
-            let offset = if !rotate || matches!(self.vision, TwoDimDirection::East) {
-                Vec2::new(15.0 * zoom, 10.0 * zoom)
-            } else {
-                Vec2::new(-15.0 * zoom, 10.0 * zoom)
-            };
-            tileset.draw_region(
-                ctx,
-                item.looks_like(),
-                DrawParams::new()
-                    .position(position + offset)
-                    .scale(scale * -1.0),
-            );
+            Soul::Dog(identity, ..) => {
+                tileset.draw_region(
+                    ctx,
+                    "dog",
+                    DrawParams::new()
+                        .position(position)
+                        .scale(scale)
+                        .color(identity.fur_color.into()),
+                );
+            }
+            Soul::ZombieDog(identity, ..) => {
+                let freshness = self
+                    .body
+                    .parts
+                    .get(&Point::new(0, 0))
+                    .map_or(Freshness::Rotten, BodyPart::freshness);
+                let (name, color) = match freshness {
+                    Freshness::Fresh => ("raw_zombie_dog", identity.fur_color.into()),
+                    Freshness::Rotten => ("zombie_dog", Colors::WHITE),
+                    Freshness::Skeletal => ("skeleton_dog", Colors::WARM_IVORY),
+                };
+                tileset.draw_region(
+                    ctx,
+                    name,
+                    DrawParams::new()
+                        .position(position)
+                        .scale(scale)
+                        .color(color),
+                );
+            }
         }
     }
 }
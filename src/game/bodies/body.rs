@@ -1,13 +1,52 @@
 use std::collections::HashMap;
 
 use geometry::Point;
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use super::super::{map::items::BodyPart, Item};
+use super::{
+    super::{
+        map::{
+            item::{ItemInteract, WearSlot},
+            items::{BodyPart, BodyPartType},
+        },
+        Item,
+    },
+    Capabilities, Damage, DamageResult, DamageType, Decay,
+};
 
 pub type BodyPartsCollections = HashMap<Point, BodyPart>;
 
+/// Path to the body part: position of the root part and names of the parts leading down to it
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct BodyPartPath {
+    #[serde(rename = "r")]
+    pub root: Point,
+    #[serde(rename = "n")]
+    pub names: Vec<String>,
+}
+
+impl BodyPartPath {
+    pub fn new(root: Point, names: Vec<String>) -> Self {
+        Self { root, names }
+    }
+
+    pub fn root(root: Point) -> Self {
+        Self::new(root, Vec::new())
+    }
+
+    pub fn child<S: Into<String>>(&self, name: S) -> Self {
+        let mut names = self.names.clone();
+        names.push(name.into());
+        Self::new(self.root, names)
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Body {
@@ -25,4 +64,225 @@ impl Body {
             wear: Vec::default(),
         }
     }
+
+    pub fn get_part(&self, path: &BodyPartPath) -> Option<&BodyPart> {
+        let mut part = self.parts.get(&path.root)?;
+        for name in &path.names {
+            part = part.child(name)?;
+        }
+        Some(part)
+    }
+
+    pub fn get_part_mut(&mut self, path: &BodyPartPath) -> Option<&mut BodyPart> {
+        let mut part = self.parts.get_mut(&path.root)?;
+        for name in &path.names {
+            part = part.child_mut(name)?;
+        }
+        Some(part)
+    }
+
+    /// Removes body part with all its children from the body, root parts can't be detached
+    pub fn detach(&mut self, path: &BodyPartPath) -> Option<BodyPart> {
+        let (name, parent) = path.names.split_last()?;
+        self.get_part_mut(&BodyPartPath::new(path.root, parent.to_vec()))?
+            .remove_child(name)
+    }
+
+    /// Attaches body part outside or inside of the parent, returns new path to the attached part
+    pub fn attach(
+        &mut self,
+        parent: &BodyPartPath,
+        part: BodyPart,
+        inside: bool,
+    ) -> Option<BodyPartPath> {
+        let name = self.get_part_mut(parent)?.attach(part, inside);
+        Some(parent.child(name))
+    }
+
+    /// Checks if the part is attached to a slot it doesn't naturally belong to
+    pub fn is_misplaced(&self, path: &BodyPartPath) -> bool {
+        if let Some((name, parent)) = path.names.split_last() {
+            if let Some(parent) = self.get_part(&BodyPartPath::new(path.root, parent.to_vec())) {
+                if let Some(part) = parent.child(name) {
+                    return !parent.fits(part, self.is_inside(path));
+                }
+            }
+        }
+        false
+    }
+
+    /// Checks if the part is placed inside of its parent
+    pub fn is_inside(&self, path: &BodyPartPath) -> bool {
+        if let Some((name, parent)) = path.names.split_last() {
+            if let Some(parent) = self.get_part(&BodyPartPath::new(path.root, parent.to_vec())) {
+                return parent.inside.iter().any(|bp| &bp.name == name);
+            }
+        }
+        false
+    }
+
+    /// Wounds the part, piercing damage partially goes to one of the inner organs.
+    /// Destroyed outer parts fall off, destroyed inner organs are gone.
+    pub fn damage<R: Rng + ?Sized>(
+        &mut self,
+        path: &BodyPartPath,
+        damage: Damage,
+        rng: &mut R,
+    ) -> DamageResult {
+        let mut result = DamageResult::default();
+        let mut targets = vec![(path.clone(), damage.amount)];
+        if damage.typ == DamageType::Pierce {
+            let organ = self
+                .get_part(path)
+                .and_then(|part| part.inside.choose(rng))
+                .map(|organ| organ.name.clone());
+            if let Some(organ) = organ {
+                targets[0].1 -= damage.amount / 2;
+                targets.push((path.child(organ), damage.amount / 2));
+            }
+        }
+        for (path, amount) in targets {
+            let inside = self.is_inside(&path);
+            let destroyed = if let Some(part) = self.get_part_mut(&path) {
+                let amount = (f64::from(amount) * damage.typ.multiplier(part)).round() as u32;
+                if amount == 0 {
+                    continue;
+                }
+                let data = part.organ_data_mut();
+                data.wounds = data.wounds.saturating_add(amount);
+                result.damaged.push(part.name.clone());
+                if part.hp() > 0 {
+                    continue;
+                }
+                if part.is_vital() || path.is_root() {
+                    result.fatal = true;
+                    continue;
+                }
+                true
+            } else {
+                false
+            };
+            if destroyed {
+                if let Some(part) = self.detach(&path) {
+                    if !inside {
+                        result.fallen.push(part);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Mass of all body parts and worn items in grams
+    pub fn mass(&self) -> u32 {
+        self.parts.values().map(ItemInteract::mass).sum::<u32>()
+            + self.wear.iter().map(ItemInteract::mass).sum::<u32>()
+    }
+
+    /// Progresses decay of every part, soft tissues could rot away.
+    /// Returns true if some part has become less fresh or rotted away.
+    pub fn decay(&mut self, ticks: u32) -> bool {
+        let mut changed = false;
+        self.parts.retain(|_, part| {
+            let decay = part.decay(ticks);
+            changed |= decay != Decay::Same;
+            decay != Decay::RottedAway
+        });
+        changed
+    }
+
+    /// What this body is able to do, counts only parts attached to their natural places
+    pub fn capabilities(&self) -> Capabilities {
+        let mut capabilities = Capabilities::default();
+        for root in self.parts.values() {
+            capabilities.add_root(root);
+        }
+        capabilities
+    }
+
+    /// Paths to every body part, parents go before their children
+    pub fn paths(&self) -> Vec<BodyPartPath> {
+        let mut roots: Vec<&Point> = self.parts.keys().collect();
+        roots.sort_by_key(|p| (p.y, p.x));
+        let mut paths = Vec::new();
+        for root in roots {
+            let path = BodyPartPath::root(*root);
+            paths.push(path.clone());
+            self.parts[root].collect_paths(&path, &mut paths);
+        }
+        paths
+    }
+
+    /// Random part that could be hit from outside, bigger parts are hit more often
+    pub fn random_hit_location<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<BodyPartPath> {
+        let outer: Vec<BodyPartPath> = self
+            .paths()
+            .into_iter()
+            .filter(|path| {
+                (1..=path.names.len()).all(|len| {
+                    !self.is_inside(&BodyPartPath::new(path.root, path.names[..len].to_vec()))
+                })
+            })
+            .collect();
+        outer
+            .choose_weighted(rng, |path| self.get_part(path).map_or(0, BodyPart::max_hp))
+            .ok()
+            .cloned()
+    }
+
+    /// Paths to every non-root body part, parents go before their children
+    pub fn detachable_paths(&self) -> Vec<BodyPartPath> {
+        self.paths()
+            .into_iter()
+            .filter(|path| !path.is_root())
+            .collect()
+    }
+
+    /// Checks if there is a body part to put clothing of this slot on
+    pub fn has_slot(&self, slot: WearSlot) -> bool {
+        match slot {
+            WearSlot::Head => self.paths().iter().any(|path| {
+                matches!(
+                    self.get_part(path).map(|part| &part.typ),
+                    Some(BodyPartType::HumanHead(..) | BodyPartType::DogHead(..))
+                )
+            }),
+            WearSlot::Torso | WearSlot::Shoulders => self
+                .parts
+                .values()
+                .any(|part| matches!(part.typ, BodyPartType::HumanTorso(..))),
+        }
+    }
+
+    /// Root part with a head closest to the front, the creature looks from there
+    pub fn head_root(&self) -> Point {
+        self.parts
+            .iter()
+            .filter(|(_, part)| {
+                part.outside.iter().any(|p| {
+                    matches!(
+                        p.typ,
+                        BodyPartType::HumanHead(..) | BodyPartType::DogHead(..)
+                    )
+                })
+            })
+            .map(|(pos, _)| *pos)
+            .min_by_key(|p| (p.y, p.x))
+            .unwrap_or_else(|| Point::new(0, 0))
+    }
+
+    /// Index of the worn item occupying the slot
+    pub fn worn_on(&self, slot: WearSlot) -> Option<usize> {
+        self.wear.iter().position(|i| i.wear_slot() == Some(slot))
+    }
+
+    /// Human-readable path like "torso > head > left eye"
+    pub fn describe(&self, path: &BodyPartPath) -> String {
+        let mut names = Vec::with_capacity(path.names.len() + 1);
+        if let Some(root) = self.parts.get(&path.root) {
+            names.push(root.name.as_str());
+        }
+        names.extend(path.names.iter().map(String::as_str));
+        names.join(" > ")
+    }
 }
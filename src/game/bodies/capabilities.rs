@@ -0,0 +1,101 @@
+use super::{
+    super::map::items::{BodyPart, BodyPartType},
+    Damage, DamageType,
+};
+
+/// What a creature is able to do with its body.
+/// Only body parts attached to their natural places are counted.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Capabilities {
+    pub legs: u8,
+    pub normal_legs: u8,
+    pub hands: u8,
+    pub eyes: u8,
+    pub ears: u8,
+    pub brain: bool,
+    pub maws: u8,
+    pub mouths: u8,
+}
+
+impl Capabilities {
+    pub(crate) fn add_root(&mut self, root: &BodyPart) {
+        match root.typ {
+            BodyPartType::HumanTorso(..) => self.normal_legs += 2,
+            BodyPartType::DogTorso(..) => self.normal_legs += 4,
+            _ => {}
+        }
+        self.add_children(root);
+    }
+
+    fn add_children(&mut self, parent: &BodyPart) {
+        let outside = parent.outside.iter().map(|p| (p, false));
+        let inside = parent.inside.iter().map(|p| (p, true));
+        for (part, is_inside) in outside.chain(inside) {
+            if !parent.fits(part, is_inside) {
+                continue;
+            }
+            match part.typ {
+                BodyPartType::HumanLeftLeg(..)
+                | BodyPartType::HumanRightLeg(..)
+                | BodyPartType::DogPaw(..) => self.legs += 1,
+                BodyPartType::HumanLeftHand(..) | BodyPartType::HumanRightHand(..) => {
+                    self.hands += 1;
+                }
+                BodyPartType::HumanEye(..) | BodyPartType::DogEye(..) => self.eyes += 1,
+                BodyPartType::HumanEar(..) | BodyPartType::DogEar(..) => self.ears += 1,
+                BodyPartType::HumanBrain(..) | BodyPartType::DogBrain(..) => self.brain = true,
+                BodyPartType::DogMaw(..) => self.maws += 1,
+                BodyPartType::HumanMouth(..) => self.mouths += 1,
+                _ => {}
+            }
+            self.add_children(part);
+        }
+    }
+
+    /// Multiplier for the walking time, creatures without legs are crawling
+    pub fn walk_k(&self) -> f32 {
+        if self.legs == 0 {
+            10.0
+        } else {
+            (f32::from(self.normal_legs) / f32::from(self.legs)).max(1.0)
+        }
+    }
+
+    /// Multiplier for the time of dressing and undressing, it's impossible without hands
+    pub fn dress_k(&self) -> Option<f32> {
+        match self.hands {
+            0 => None,
+            1 => Some(2.0),
+            _ => Some(1.0),
+        }
+    }
+
+    pub fn vision_range(&self, max: i32) -> i32 {
+        match self.eyes {
+            0 => 0,
+            1 => max * 2 / 3,
+            _ => max,
+        }
+    }
+
+    pub fn hearing_range(&self, max: i32) -> i32 {
+        match self.ears {
+            0 => 0,
+            1 => max * 2 / 3,
+            _ => max,
+        }
+    }
+
+    /// The best body part to fight with when there is no weapon in hands
+    pub fn natural_weapon(&self) -> Option<Damage> {
+        if self.maws > 0 {
+            Some(Damage::new(DamageType::Bite, 10))
+        } else if self.hands > 0 {
+            Some(Damage::new(DamageType::Blunt, 4))
+        } else if self.mouths > 0 {
+            Some(Damage::new(DamageType::Bite, 3))
+        } else {
+            None
+        }
+    }
+}
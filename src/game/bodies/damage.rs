@@ -0,0 +1,59 @@
+use super::super::map::items::BodyPart;
+use super::Freshness;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DamageType {
+    #[serde(rename = "c")]
+    Cut,
+    #[serde(rename = "b")]
+    Blunt,
+    #[serde(rename = "p")]
+    Pierce,
+    #[serde(rename = "t")]
+    Bite,
+}
+
+impl DamageType {
+    pub fn participle(self) -> &'static str {
+        match self {
+            Self::Cut => "cut",
+            Self::Blunt => "smashed",
+            Self::Pierce => "pierced",
+            Self::Bite => "bitten",
+        }
+    }
+
+    /// Bones are easier to smash but harder to chew
+    pub fn multiplier(self, part: &BodyPart) -> f64 {
+        match (self, part.freshness()) {
+            (Self::Blunt, Freshness::Skeletal) => 1.5,
+            (Self::Bite, Freshness::Skeletal) => 0.5,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
+pub struct Damage {
+    #[serde(rename = "t")]
+    pub typ: DamageType,
+    #[serde(rename = "a")]
+    pub amount: u32,
+}
+
+impl Damage {
+    pub fn new(typ: DamageType, amount: u32) -> Self {
+        Self { typ, amount }
+    }
+}
+
+/// What happened to the body after it took damage
+#[derive(Debug, Default)]
+pub struct DamageResult {
+    /// Names of the damaged parts
+    pub damaged: Vec<String>,
+    /// Destroyed limbs that fell off
+    pub fallen: Vec<BodyPart>,
+    /// Vital part was destroyed
+    pub fatal: bool,
+}
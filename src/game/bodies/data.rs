@@ -1,7 +1,7 @@
 use rand::distributions::{Distribution, Standard};
 use rand::Rng;
 
-use super::super::human::Personality;
+use super::super::{animals::AnimalIdentity, human::Personality};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
 pub enum Freshness {
@@ -21,6 +21,32 @@ impl Freshness {
             Self::Skeletal => "skeletal",
         }
     }
+
+    /// How many ticks of decay this stage lasts, skeletons don't decay further
+    pub fn duration(self) -> Option<u32> {
+        match self {
+            Self::Fresh => Some(3 * 24 * 60 * 60),
+            Self::Rotten => Some(30 * 24 * 60 * 60),
+            Self::Skeletal => None,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Fresh => Self::Rotten,
+            Self::Rotten | Self::Skeletal => Self::Skeletal,
+        }
+    }
+}
+
+/// What decay has done to a body part
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Decay {
+    /// Only the counter of decay ticks has grown
+    Same,
+    /// The part or some of its children became less fresh or rotted away
+    Changed,
+    RottedAway,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
@@ -61,6 +87,14 @@ pub struct OrganData {
     pub size: BodySize,
     #[serde(rename = "l")]
     pub alive: bool,
+    /// Ticks of decay passed since current freshness stage began
+    #[serde(default)]
+    #[serde(rename = "d")]
+    pub decay: u32,
+    /// Damage taken, part is destroyed when it reaches max HP
+    #[serde(default)]
+    #[serde(rename = "w")]
+    pub wounds: u32,
 }
 
 // TODO: SkinData with SkinTone and scars/tattoo/etc.
@@ -72,6 +106,19 @@ impl OrganData {
             age: character.appearance.age,
             size: character.appearance.body_size,
             alive: character.mind.alive,
+            decay: 0,
+            wounds: 0,
+        }
+    }
+
+    pub fn animal(identity: &AnimalIdentity, freshness: Freshness) -> Self {
+        Self {
+            freshness,
+            age: identity.age,
+            size: identity.body_size,
+            alive: identity.alive,
+            decay: 0,
+            wounds: 0,
         }
     }
 }
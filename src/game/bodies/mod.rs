@@ -1,7 +1,11 @@
-pub use body::Body;
-pub use data::{BodySize, Freshness, OrganData};
+pub use body::{Body, BodyPartPath};
+pub use capabilities::Capabilities;
+pub use damage::{Damage, DamageResult, DamageType};
+pub use data::{BodySize, Decay, Freshness, OrganData};
 pub use sex::Sex;
 
 mod body;
+mod capabilities;
+mod damage;
 mod data;
 mod sex;
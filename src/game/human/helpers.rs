@@ -213,9 +213,9 @@ mod tests {
     use super::{
         super::{
             super::{
-                bodies::{BodySize, Freshness, OrganData, Sex},
+                bodies::{BodyPartPath, BodySize, Freshness, OrganData, Sex},
                 map::items::{BodyPart, BodyPartType},
-                ItemView,
+                ItemInteract, ItemView,
             },
             tests::personality::{dead_boy, old_queer, tester_girl},
             Appearance, Gender, HairColor, MainHand, Mind, Personality, SkinTone,
@@ -235,6 +235,8 @@ mod tests {
                     OrganData {
                         age: 15,
                         alive: true,
+                        decay: 0,
+                        wounds: 0,
                         size: BodySize::Small,
                         freshness: Freshness::Fresh,
                     },
@@ -254,6 +256,8 @@ mod tests {
                         age: 15,
                         size: BodySize::Small,
                         alive: true,
+                        decay: 0,
+                        wounds: 0,
                     },
                     Personality {
                         appearance: Appearance {
@@ -285,6 +289,8 @@ mod tests {
                             age: 15,
                             size: BodySize::Small,
                             alive: true,
+                            decay: 0,
+                            wounds: 0,
                         }),
                         ..
                     }
@@ -304,6 +310,8 @@ mod tests {
                                 age: 15,
                                 size: BodySize::Small,
                                 alive: true,
+                                decay: 0,
+                                wounds: 0,
                             },
                             SkinTone::WarmIvory
                         ),
@@ -326,6 +334,8 @@ mod tests {
                     OrganData {
                         age: 9,
                         alive: false,
+                        decay: 0,
+                        wounds: 0,
                         size: BodySize::Tiny,
                         freshness: Freshness::Rotten,
                     },
@@ -384,6 +394,8 @@ mod tests {
                     OrganData {
                         age: 9,
                         alive: false,
+                        decay: 0,
+                        wounds: 0,
                         size: BodySize::Tiny,
                         freshness: Freshness::Skeletal,
                     },
@@ -424,6 +436,8 @@ mod tests {
                     OrganData {
                         age: 15,
                         alive: true,
+                        decay: 0,
+                        wounds: 0,
                         freshness: Freshness::Fresh,
                         size: BodySize::Small,
                     },
@@ -511,6 +525,8 @@ mod tests {
                     age: 75,
                     size: BodySize::Large,
                     alive: true,
+                    decay: 0,
+                    wounds: 0,
                 },
                 HairColor::Gray,
                 SkinTone::Almond,
@@ -533,6 +549,8 @@ mod tests {
                     OrganData {
                         age: 9,
                         alive: false,
+                        decay: 0,
+                        wounds: 0,
                         size: BodySize::Tiny,
                         freshness: Freshness::Fresh,
                     },
@@ -553,6 +571,8 @@ mod tests {
                     OrganData {
                         age: 9,
                         alive: false,
+                        decay: 0,
+                        wounds: 0,
                         size: BodySize::Tiny,
                         freshness: Freshness::Fresh,
                     },
@@ -573,6 +593,8 @@ mod tests {
                     OrganData {
                         age: 9,
                         alive: false,
+                        decay: 0,
+                        wounds: 0,
                         size: BodySize::Tiny,
                         freshness: Freshness::Fresh,
                     },
@@ -584,4 +606,19 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn test_body_mass() {
+        let boy = human_body(&dead_boy(), Freshness::Fresh);
+        let mut old = human_body(&old_queer(), Freshness::Fresh);
+        assert!(boy.mass() < old.mass());
+        assert!((60_000..100_000).contains(&old.mass()));
+
+        let whole = old.mass();
+        let leg = old
+            .detach(&BodyPartPath::root(Point::new(0, 0)).child("left leg"))
+            .unwrap();
+        assert!(leg.mass() > 0);
+        assert_eq!(whole, old.mass() + leg.mass());
+    }
 }
@@ -1,40 +1,53 @@
 #![allow(dead_code)]
 
-use std::collections::{hash_map::DefaultHasher, HashSet};
-use std::hash::{Hash, Hasher};
+use std::collections::HashSet;
 
 use arrayvec::ArrayVec;
-use rand::{distributions::Standard, rngs::StdRng, Rng, SeedableRng};
+use geometry::Point;
+use rand::{distributions::Standard, seq::SliceRandom, Rng, SeedableRng};
 
 use super::{
-    super::human::Personality,
-    items::Shovel,
+    super::{
+        animals::{helpers::dog_body, AnimalIdentity},
+        bodies::Freshness,
+        human::Personality,
+        world::{seed_hash, WorldRng},
+        Avatar,
+    },
+    items::{Corpse, Sack, Shovel},
+    terrain::TerrainInteract,
     terrains::{Boulder, Dirt, Grass, Grave, GraveData, GraveVariant, Tree},
-    ChunkPos, Tile,
+    ChunkPos, Item, Tile, TilePos,
 };
 
-#[derive(Hash)]
-struct ChunkUnique {
-    pos: ChunkPos,
-    world_seed: String,
-}
+/// No one lives this close to the starting point, in chunks
+const QUIET_RADIUS: i32 = 2;
+/// Graveyards with this many graves have a watchman
+const GRAVEYARD_SIZE: usize = 8;
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct Chunk {
     pub pos: ChunkPos,
     pub tiles: ArrayVec<Tile, { Chunk::USIZE }>,
+    /// Tick up to which decay was simulated, chunks far from player catch up later
+    #[serde(default)]
+    #[serde(rename = "d")]
+    pub decayed_at: Option<u128>,
 }
 
 impl Chunk {
     pub const SIZE: i32 = 32;
     pub const USIZE: usize = (Chunk::SIZE * Chunk::SIZE) as usize;
 
-    pub fn generate(world_seed: String, pos: ChunkPos) -> Self {
-        let mut hasher = DefaultHasher::new();
-        let seed = ChunkUnique { pos, world_seed };
-        seed.hash(&mut hasher);
-        let seed = hasher.finish();
-        let mut rng = StdRng::seed_from_u64(seed);
+    /// Seed of the chunk generator, it's hashed the same way as the world seed
+    /// to generate the same chunk with every build
+    fn seed(world_seed: &str, pos: ChunkPos) -> u64 {
+        seed_hash(format!("{}:{}:{}", world_seed, pos.x, pos.y).as_str())
+    }
+
+    /// New chunk and units living there
+    pub fn generate(world_seed: String, pos: ChunkPos) -> (Self, Vec<Avatar>) {
+        let mut rng = WorldRng::seed_from_u64(Self::seed(world_seed.as_str(), pos));
         let mut tiles = ArrayVec::new();
         for _ in 0..Chunk::USIZE {
             tiles.push(Tile::new(if rng.gen_bool(0.005) {
@@ -48,6 +61,7 @@ impl Chunk {
             }));
         }
         let count: usize = rng.gen_range(5..20);
+        let mut graves = 0;
         let mut blocked_tiles = HashSet::with_capacity(100);
         for _ in 0..count {
             let mut pos = rng.gen_range(0..Chunk::USIZE) as usize;
@@ -68,8 +82,27 @@ impl Chunk {
                 blocked_tiles.insert(pos + Chunk::SIZE as usize);
             }
             if rng.gen_bool(0.1) {
-                tiles.get_mut(pos).unwrap().items.push(Shovel::new().into());
+                let item: Item = if rng.gen_bool(0.7) {
+                    Shovel::new().into()
+                } else {
+                    Sack::new().into()
+                };
+                tiles.get_mut(pos).unwrap().items.push(item);
+            } else if rng.gen_bool(0.05) {
+                let identity = AnimalIdentity::random(&mut rng, false);
+                let freshness = if rng.gen_bool(0.5) {
+                    Freshness::Rotten
+                } else {
+                    Freshness::Skeletal
+                };
+                let body = dog_body(&identity, freshness);
+                tiles
+                    .get_mut(pos)
+                    .unwrap()
+                    .items
+                    .push(Corpse::animal(identity, body).into());
             } else {
+                graves += 1;
                 let death_year = rng.gen_range(200..=255);
                 tiles.get_mut(pos).unwrap().terrain = Grave::new(
                     if death_year < 200 {
@@ -85,6 +118,85 @@ impl Chunk {
                 .into();
             }
         }
-        Chunk { pos, tiles }
+        let units = if pos.x.abs() > QUIET_RADIUS || pos.y.abs() > QUIET_RADIUS {
+            Self::populate(&mut rng, pos, &tiles, &blocked_tiles, graves)
+        } else {
+            Vec::new()
+        };
+        (
+            Chunk {
+                pos,
+                tiles,
+                decayed_at: None,
+            },
+            units,
+        )
+    }
+
+    fn populate(
+        rng: &mut WorldRng,
+        pos: ChunkPos,
+        tiles: &ArrayVec<Tile, { Chunk::USIZE }>,
+        blocked_tiles: &HashSet<usize>,
+        graves: usize,
+    ) -> Vec<Avatar> {
+        let mut free: Vec<usize> = (0..Chunk::USIZE)
+            .filter(|i| !blocked_tiles.contains(i) && tiles[*i].terrain.is_passable())
+            .collect();
+        free.shuffle(rng);
+        let mut free = free.into_iter().map(|i| Point::from_chunk(pos, i));
+        let mut units = Vec::new();
+        if graves >= GRAVEYARD_SIZE && rng.gen_bool(0.3) {
+            if let Some(post) = free.next() {
+                units.push(Avatar::watchman(Personality::random(rng, true), post));
+            }
+        }
+        if rng.gen_bool(0.1) {
+            if let Some(home) = free.next() {
+                units.push(Avatar::villager(Personality::random(rng, true), home));
+            }
+        }
+        if rng.gen_bool(0.1) {
+            let count = rng.gen_range(1..=3);
+            for home in free.take(count) {
+                let identity = AnimalIdentity::random(rng, true);
+                let body = dog_body(&identity, Freshness::Fresh);
+                units.push(Avatar::dog(identity, body, home));
+            }
+        }
+        units
+    }
+
+    /// Simulates decay for all the time passed since the last call.
+    /// Returns false if nothing has rotted, then there is no need to save the chunk.
+    pub fn decay(&mut self, current_tick: u128) -> bool {
+        let mut changed = false;
+        if let Some(decayed_at) = self.decayed_at {
+            let ticks = u32::try_from(current_tick.saturating_sub(decayed_at)).unwrap_or(u32::MAX);
+            if ticks > 0 {
+                for tile in &mut self.tiles {
+                    changed |= tile.decay(ticks);
+                }
+            }
+        }
+        self.decayed_at = Some(current_tick);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Chunk, ChunkPos};
+
+    #[test]
+    fn test_chunk_seed() {
+        assert_eq!(
+            0x8fec_6eec_7c09_b455,
+            Chunk::seed("test", ChunkPos::new(1, -2))
+        );
+        assert_ne!(
+            Chunk::seed("test", ChunkPos::new(1, 2)),
+            Chunk::seed("test", ChunkPos::new(2, 1))
+        );
     }
 }
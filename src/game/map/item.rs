@@ -4,8 +4,8 @@ use enum_dispatch::enum_dispatch;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    super::Avatar,
-    items::{Axe, BodyPart, Cloak, Corpse, Gravestone, Hat, Knife, Rags, Shovel},
+    super::{bodies::Damage, Avatar},
+    items::{Axe, BodyPart, Cloak, Container, Corpse, Gravestone, Hat, Knife, Rags, Sack, Shovel},
 };
 
 #[enum_dispatch]
@@ -21,6 +21,7 @@ pub enum Item {
     Hat,
     Cloak,
     Rags,
+    Sack,
 }
 
 #[enum_dispatch(Item)]
@@ -35,6 +36,16 @@ pub trait ItemInteract {
         HashSet::new()
     }
     fn mass(&self) -> u32; // in grams
+    /// in milliliters, most things are about as dense as water
+    fn volume(&self) -> u32 {
+        self.mass()
+    }
+    fn container(&self) -> Option<&Container> {
+        None
+    }
+    fn container_mut(&mut self) -> Option<&mut Container> {
+        None
+    }
     fn wield_time(&self, _avatar: &Avatar) -> f64 {
         // 100 grams per tick
         self.mass() as f64 / 100.0
@@ -49,8 +60,46 @@ pub trait ItemInteract {
     fn read(&self) -> String {
         unreachable!()
     }
+    fn wear_slot(&self) -> Option<WearSlot> {
+        None
+    }
     fn is_wearable(&self) -> bool {
-        false
+        self.wear_slot().is_some()
+    }
+    /// grams of flesh per tick that could be cut with this item
+    fn butch_speed(&self) -> f64 {
+        0.0
+    }
+    /// damage dealt when this item is used as a melee weapon
+    fn melee_damage(&self) -> Option<Damage> {
+        None
+    }
+}
+
+/// Place on the body where clothing goes, only one item fits each slot
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WearSlot {
+    Head,
+    Torso,
+    Shoulders,
+}
+
+impl WearSlot {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Head => "head",
+            Self::Torso => "torso",
+            Self::Shoulders => "shoulders",
+        }
+    }
+
+    /// Ticks to put on an item with both hands, taking it off is twice faster
+    pub fn dress_time(self) -> u32 {
+        match self {
+            Self::Head => 20,
+            Self::Torso => 100,
+            Self::Shoulders => 50,
+        }
     }
 }
 
@@ -71,7 +120,9 @@ mod tests {
                 bodies::{Freshness, OrganData},
                 human::{helpers::human_body, tests::personality::dead_boy},
             },
-            items::{Axe, BodyPart, BodyPartType, Cloak, Corpse, Gravestone, Hat, Knife, Shovel},
+            items::{
+                Axe, BodyPart, BodyPartType, Cloak, Corpse, Gravestone, Hat, Knife, Sack, Shovel,
+            },
         },
         Item, ItemInteract, ItemTag, ItemView,
     };
@@ -160,5 +211,23 @@ mod tests {
         let cloak: Item = Cloak::new().into();
         assert_eq!("cloak", cloak.name());
         assert!(cloak.is_wearable());
+        assert!(cloak.container().is_some());
+    }
+
+    #[test]
+    fn test_sack() {
+        let mut sack: Item = Sack::new().into();
+        assert_eq!("empty sack", sack.name());
+        let mass = sack.mass();
+
+        let character = dead_boy();
+        let body = human_body(&character, Freshness::Rotten);
+        let corpse: Item = Corpse::new(character, body).into();
+        let container = sack.container_mut().unwrap();
+        assert!(container.fits(&corpse));
+        container.items.push(corpse.clone());
+        assert!(!Cloak::new().pockets.fits(&corpse));
+        assert_eq!("sack", sack.name());
+        assert_eq!(mass + corpse.mass(), sack.mass());
     }
 }
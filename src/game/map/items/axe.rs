@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
-use super::super::item::{ItemInteract, ItemTag, ItemView};
+use super::super::{
+    super::bodies::{Damage, DamageType},
+    item::{ItemInteract, ItemTag, ItemView},
+};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Axe {}
@@ -35,4 +38,12 @@ impl ItemInteract for Axe {
     fn mass(&self) -> u32 {
         1_000 // 1kg axe
     }
+
+    fn butch_speed(&self) -> f64 {
+        20.0
+    }
+
+    fn melee_damage(&self) -> Option<Damage> {
+        Some(Damage::new(DamageType::Cut, 20))
+    }
 }
@@ -1,7 +1,7 @@
 use super::super::{
     super::{
-        animals::FurColor,
-        bodies::{Freshness, OrganData, Sex},
+        animals::{AnimalIdentity, FurColor},
+        bodies::{BodyPartPath, BodySize, Decay, Freshness, OrganData, Sex},
         human::{age_name, Gender, HairColor, Personality, SkinTone},
     },
     item::{ItemInteract, ItemView},
@@ -39,6 +39,122 @@ impl BodyPart {
         self
     }
 
+    pub fn child(&self, name: &str) -> Option<&BodyPart> {
+        self.outside
+            .iter()
+            .chain(self.inside.iter())
+            .find(|bp| bp.name == name)
+    }
+
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut BodyPart> {
+        self.outside
+            .iter_mut()
+            .chain(self.inside.iter_mut())
+            .find(|bp| bp.name == name)
+    }
+
+    pub fn remove_child(&mut self, name: &str) -> Option<BodyPart> {
+        if let Some(i) = self.outside.iter().position(|bp| bp.name == name) {
+            return Some(self.outside.remove(i));
+        }
+        self.inside
+            .iter()
+            .position(|bp| bp.name == name)
+            .map(|i| self.inside.remove(i))
+    }
+
+    /// Attaches part outside or inside, renames it to "second ..." if the name is taken
+    pub fn attach(&mut self, mut part: BodyPart, inside: bool) -> String {
+        let name = part.name.clone();
+        let mut n = 1;
+        while self.child(part.name.as_str()).is_some() {
+            n += 1;
+            part.name = format!("{} {}", ordinal(n), name);
+        }
+        let name = part.name.clone();
+        if inside {
+            self.inside.push(part);
+        } else {
+            self.outside.push(part);
+        }
+        name
+    }
+
+    /// Checks if part naturally belongs outside or inside of this one
+    pub fn fits(&self, part: &BodyPart, inside: bool) -> bool {
+        match (&self.typ, inside) {
+            (BodyPartType::HumanTorso(..), false) => matches!(
+                part.typ,
+                BodyPartType::HumanHead(..)
+                    | BodyPartType::HumanLeftArm(..)
+                    | BodyPartType::HumanRightArm(..)
+                    | BodyPartType::HumanLeftLeg(..)
+                    | BodyPartType::HumanRightLeg(..)
+            ),
+            (BodyPartType::HumanTorso(..), true) => matches!(
+                part.typ,
+                BodyPartType::HumanHeart(..)
+                    | BodyPartType::HumanStomach(..)
+                    | BodyPartType::HumanLung(..)
+                    | BodyPartType::HumanKidney(..)
+                    | BodyPartType::HumanLiver(..)
+                    | BodyPartType::HumanIntestines(..)
+            ),
+            (BodyPartType::HumanHead(..), false) => matches!(
+                part.typ,
+                BodyPartType::HumanEye(..)
+                    | BodyPartType::HumanNose(..)
+                    | BodyPartType::HumanMouth(..)
+                    | BodyPartType::HumanEar(..)
+            ),
+            (BodyPartType::HumanHead(..), true) => {
+                matches!(part.typ, BodyPartType::HumanBrain(..))
+            }
+            (BodyPartType::HumanLeftArm(..), false) => {
+                matches!(part.typ, BodyPartType::HumanLeftHand(..))
+            }
+            (BodyPartType::HumanRightArm(..), false) => {
+                matches!(part.typ, BodyPartType::HumanRightHand(..))
+            }
+            (BodyPartType::HumanLeftLeg(..), false) => {
+                matches!(part.typ, BodyPartType::HumanLeftFoot(..))
+            }
+            (BodyPartType::HumanRightLeg(..), false) => {
+                matches!(part.typ, BodyPartType::HumanRightFoot(..))
+            }
+            (BodyPartType::DogTorso(..), false) => matches!(
+                part.typ,
+                BodyPartType::DogHead(..) | BodyPartType::DogPaw(..) | BodyPartType::DogTail(..)
+            ),
+            (BodyPartType::DogTorso(..), true) => matches!(
+                part.typ,
+                BodyPartType::DogHeart(..)
+                    | BodyPartType::DogStomach(..)
+                    | BodyPartType::DogLung(..)
+                    | BodyPartType::DogKidney(..)
+                    | BodyPartType::DogLiver(..)
+                    | BodyPartType::DogIntestines(..)
+            ),
+            (BodyPartType::DogHead(..), false) => matches!(
+                part.typ,
+                BodyPartType::DogMaw(..)
+                    | BodyPartType::DogEye(..)
+                    | BodyPartType::DogNose(..)
+                    | BodyPartType::DogEar(..)
+            ),
+            (BodyPartType::DogHead(..), true) => matches!(part.typ, BodyPartType::DogBrain(..)),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn collect_paths(&self, path: &BodyPartPath, paths: &mut Vec<BodyPartPath>) {
+        for part in self.outside.iter().chain(self.inside.iter()) {
+            let child = path.child(part.name.as_str());
+            paths.push(child.clone());
+            part.collect_paths(&child, paths);
+        }
+    }
+
     pub fn organ_data(&self) -> &OrganData {
         match &self.typ {
             BodyPartType::HumanHead(data, ..)
@@ -80,6 +196,130 @@ impl BodyPart {
         }
     }
 
+    pub fn organ_data_mut(&mut self) -> &mut OrganData {
+        match &mut self.typ {
+            BodyPartType::HumanHead(data, ..)
+            | BodyPartType::HumanEye(data)
+            | BodyPartType::HumanNose(data, ..)
+            | BodyPartType::HumanMouth(data, ..)
+            | BodyPartType::HumanEar(data, ..)
+            | BodyPartType::HumanBrain(data, ..)
+            | BodyPartType::HumanTorso(data, ..)
+            | BodyPartType::HumanHeart(data, ..)
+            | BodyPartType::HumanStomach(data, ..)
+            | BodyPartType::HumanLung(data, ..)
+            | BodyPartType::HumanKidney(data, ..)
+            | BodyPartType::HumanLiver(data, ..)
+            | BodyPartType::HumanIntestines(data, ..)
+            | BodyPartType::HumanLeftArm(data, ..)
+            | BodyPartType::HumanLeftHand(data, ..)
+            | BodyPartType::HumanRightArm(data, ..)
+            | BodyPartType::HumanRightHand(data, ..)
+            | BodyPartType::HumanLeftLeg(data, ..)
+            | BodyPartType::HumanLeftFoot(data, ..)
+            | BodyPartType::HumanRightLeg(data, ..)
+            | BodyPartType::HumanRightFoot(data, ..)
+            | BodyPartType::DogHead(data, ..)
+            | BodyPartType::DogMaw(data, ..)
+            | BodyPartType::DogEye(data, ..)
+            | BodyPartType::DogNose(data, ..)
+            | BodyPartType::DogEar(data, ..)
+            | BodyPartType::DogBrain(data, ..)
+            | BodyPartType::DogTorso(data, ..)
+            | BodyPartType::DogHeart(data, ..)
+            | BodyPartType::DogStomach(data, ..)
+            | BodyPartType::DogLung(data, ..)
+            | BodyPartType::DogKidney(data, ..)
+            | BodyPartType::DogLiver(data, ..)
+            | BodyPartType::DogIntestines(data, ..)
+            | BodyPartType::DogPaw(data, ..)
+            | BodyPartType::DogTail(data, ..) => data,
+        }
+    }
+
+    pub fn max_hp(&self) -> u32 {
+        (self.base_mass() / 100).max(1)
+    }
+
+    pub fn hp(&self) -> u32 {
+        self.max_hp().saturating_sub(self.organ_data().wounds)
+    }
+
+    /// Destroying the brain or the heart of a living creature kills it
+    pub fn is_vital(&self) -> bool {
+        match self.typ {
+            BodyPartType::HumanBrain(..) | BodyPartType::DogBrain(..) => true,
+            BodyPartType::HumanHeart(..) | BodyPartType::DogHeart(..) => self.organ_data().alive,
+            _ => false,
+        }
+    }
+
+    /// Soft tissues rot away completely instead of becoming skeletal
+    pub fn is_soft(&self) -> bool {
+        matches!(
+            self.typ,
+            BodyPartType::HumanEye(..)
+                | BodyPartType::HumanNose(..)
+                | BodyPartType::HumanEar(..)
+                | BodyPartType::HumanBrain(..)
+                | BodyPartType::HumanHeart(..)
+                | BodyPartType::HumanStomach(..)
+                | BodyPartType::HumanLung(..)
+                | BodyPartType::HumanKidney(..)
+                | BodyPartType::HumanLiver(..)
+                | BodyPartType::HumanIntestines(..)
+                | BodyPartType::DogEye(..)
+                | BodyPartType::DogNose(..)
+                | BodyPartType::DogEar(..)
+                | BodyPartType::DogBrain(..)
+                | BodyPartType::DogHeart(..)
+                | BodyPartType::DogStomach(..)
+                | BodyPartType::DogLung(..)
+                | BodyPartType::DogKidney(..)
+                | BodyPartType::DogLiver(..)
+                | BodyPartType::DogIntestines(..)
+        )
+    }
+
+    /// Progresses decay of this part and its children
+    pub fn decay(&mut self, ticks: u32) -> Decay {
+        let mut changed = Self::decay_parts(&mut self.outside, ticks);
+        changed |= Self::decay_parts(&mut self.inside, ticks);
+        let soft = self.is_soft();
+        let data = self.organ_data_mut();
+        data.decay = data.decay.saturating_add(ticks);
+        while let Some(duration) = data.freshness.duration() {
+            if data.decay < duration {
+                break;
+            }
+            if soft && matches!(data.freshness, Freshness::Rotten) {
+                return Decay::RottedAway;
+            }
+            data.decay -= duration;
+            data.freshness = data.freshness.next();
+            changed = true;
+        }
+        if data.freshness.duration().is_none() {
+            data.decay = 0;
+        }
+        if changed {
+            Decay::Changed
+        } else {
+            Decay::Same
+        }
+    }
+
+    /// Returns true if some of the parts has changed or rotted away
+    fn decay_parts(parts: &mut Vec<BodyPart>, ticks: u32) -> bool {
+        let mut changed = false;
+        parts.retain_mut(|part| {
+            let decay = part.decay(ticks);
+            changed |= decay != Decay::Same;
+            decay != Decay::RottedAway
+        });
+        changed
+    }
+
     pub fn freshness(&self) -> Freshness {
         self.organ_data().freshness
     }
@@ -148,6 +388,79 @@ impl BodyPart {
             "dog"
         }
     }
+
+    /// Mass of an adult normal-sized male part without children, in grams
+    fn base_mass(&self) -> u32 {
+        match self.typ {
+            BodyPartType::HumanHead(..) => 3_500,
+            BodyPartType::HumanEye(..) | BodyPartType::DogEye(..) => 8,
+            BodyPartType::HumanNose(..) => 60,
+            BodyPartType::HumanMouth(..) => 200,
+            BodyPartType::HumanEar(..) => 50,
+            BodyPartType::HumanBrain(..) => 1_400,
+            BodyPartType::HumanTorso(..) => 25_000,
+            BodyPartType::HumanHeart(..) => 250,
+            BodyPartType::HumanStomach(..) => 125,
+            BodyPartType::HumanLung(..) => 600,
+            BodyPartType::HumanKidney(..) => 100,
+            BodyPartType::HumanLiver(..) => 1_500,
+            BodyPartType::HumanIntestines(..) => 2_000,
+            BodyPartType::HumanLeftArm(..) | BodyPartType::HumanRightArm(..) => 3_000,
+            BodyPartType::HumanLeftHand(..) | BodyPartType::HumanRightHand(..) => 500,
+            BodyPartType::HumanLeftLeg(..) | BodyPartType::HumanRightLeg(..) => 10_000,
+            BodyPartType::HumanLeftFoot(..) | BodyPartType::HumanRightFoot(..) => 750,
+            BodyPartType::DogHead(..) => 4_000,
+            BodyPartType::DogMaw(..) => 700,
+            BodyPartType::DogNose(..) => 70,
+            BodyPartType::DogEar(..) => 45,
+            BodyPartType::DogBrain(..) => 900,
+            BodyPartType::DogTorso(..) => 30_000,
+            BodyPartType::DogHeart(..) => 260,
+            BodyPartType::DogStomach(..) => 130,
+            BodyPartType::DogLung(..) => 650,
+            BodyPartType::DogKidney(..) => 120,
+            BodyPartType::DogLiver(..) => 1_600,
+            BodyPartType::DogIntestines(..) => 2_750,
+            BodyPartType::DogPaw(..) => 11_000,
+            BodyPartType::DogTail(..) => 2_500,
+        }
+    }
+
+    /// Mass of this part alone, scaled by size, age and sex
+    fn own_mass(&self) -> f64 {
+        let data = self.organ_data();
+        let k_size = match data.size {
+            BodySize::Tiny => 0.6,
+            BodySize::Small => 0.8,
+            BodySize::Normal => 1.0,
+            BodySize::Large => 1.2,
+            BodySize::Huge => 1.5,
+        };
+        let k_age = if self.is_human() {
+            // humans grow up until about sixteen years
+            (0.05 + f64::from(data.age) * 0.06).min(1.0)
+        } else if data.age < 1 {
+            0.3
+        } else {
+            1.0
+        };
+        let k_sex = match self.sex() {
+            Some(Sex::Female) => 0.85,
+            Some(Sex::Male) | None => 1.0,
+        };
+        f64::from(self.base_mass()) * k_size * k_age * k_sex
+    }
+}
+
+fn ordinal(n: usize) -> String {
+    match n {
+        1 => "first".to_string(),
+        2 => "second".to_string(),
+        3 => "third".to_string(),
+        4 => "fourth".to_string(),
+        5 => "fifth".to_string(),
+        _ => format!("{}th", n),
+    }
 }
 
 impl ItemView for BodyPart {
@@ -172,7 +485,7 @@ impl ItemView for BodyPart {
             BodyPartType::HumanNose(_, _) | BodyPartType::DogNose(_, _) => "nose",
             BodyPartType::HumanMouth(_, _, _) => "mouth",
             BodyPartType::HumanEar(_, _) | BodyPartType::DogEar(_, _) => "ear",
-            BodyPartType::HumanBrain(_, _) | BodyPartType::DogBrain(_) => "brain",
+            BodyPartType::HumanBrain(_, _) | BodyPartType::DogBrain(_, _) => "brain",
             BodyPartType::HumanTorso(_, _, _, _) | BodyPartType::DogTorso(_, _, _) => "torso",
             BodyPartType::HumanHeart(_) | BodyPartType::DogHeart(_) => "heart",
             BodyPartType::HumanStomach(_) | BodyPartType::DogStomach(_) => "stomach",
@@ -202,40 +515,13 @@ impl ItemView for BodyPart {
 
 impl ItemInteract for BodyPart {
     fn mass(&self) -> u32 {
-        // TODO: use Sex and BodySize
-        match self.typ {
-            BodyPartType::HumanHead(..) => 3_500,
-            BodyPartType::HumanEye(..) | BodyPartType::DogEye(..) => 8,
-            BodyPartType::HumanNose(..) => 60,
-            BodyPartType::HumanMouth(..) => 200,
-            BodyPartType::HumanEar(..) => 50,
-            BodyPartType::HumanBrain(..) => 1_400,
-            BodyPartType::HumanTorso(..) => 25_000,
-            BodyPartType::HumanHeart(..) => 250,
-            BodyPartType::HumanStomach(..) => 125,
-            BodyPartType::HumanLung(..) => 600,
-            BodyPartType::HumanKidney(..) => 100,
-            BodyPartType::HumanLiver(..) => 1_500,
-            BodyPartType::HumanIntestines(..) => 2_000,
-            BodyPartType::HumanLeftArm(..) | BodyPartType::HumanRightArm(..) => 3_000,
-            BodyPartType::HumanLeftHand(..) | BodyPartType::HumanRightHand(..) => 500,
-            BodyPartType::HumanLeftLeg(..) | BodyPartType::HumanRightLeg(..) => 10_000,
-            BodyPartType::HumanLeftFoot(..) | BodyPartType::HumanRightFoot(..) => 750,
-            BodyPartType::DogHead(..) => 4_000,
-            BodyPartType::DogMaw(..) => 700,
-            BodyPartType::DogNose(..) => 70,
-            BodyPartType::DogEar(..) => 45,
-            BodyPartType::DogBrain(..) => 900,
-            BodyPartType::DogTorso(..) => 30_000,
-            BodyPartType::DogHeart(..) => 260,
-            BodyPartType::DogStomach(..) => 130,
-            BodyPartType::DogLung(..) => 650,
-            BodyPartType::DogKidney(..) => 120,
-            BodyPartType::DogLiver(..) => 1_600,
-            BodyPartType::DogIntestines(..) => 2_750,
-            BodyPartType::DogPaw(..) => 11_000,
-            BodyPartType::DogTail(..) => 2_500,
-        }
+        self.own_mass().round() as u32
+            + self
+                .outside
+                .iter()
+                .chain(self.inside.iter())
+                .map(ItemInteract::mass)
+                .sum::<u32>()
     }
 }
 
@@ -268,7 +554,7 @@ pub enum BodyPartType {
     DogEye(OrganData),
     DogNose(OrganData, FurColor),
     DogEar(OrganData, FurColor),
-    DogBrain(OrganData), // TODO: dog-identity (Character-like struct with name)
+    DogBrain(OrganData, AnimalIdentity),
     DogTorso(OrganData, FurColor, Sex),
     DogHeart(OrganData),
     DogStomach(OrganData),
@@ -1,11 +1,23 @@
-use super::super::item::{ItemInteract, ItemView};
+use super::{
+    super::item::{ItemInteract, ItemView, WearSlot},
+    Container,
+};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-pub struct Cloak {}
+pub struct Cloak {
+    #[serde(rename = "p")]
+    pub pockets: Container,
+}
 
 impl Cloak {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            pockets: Self::pockets(),
+        }
+    }
+
+    fn pockets() -> Container {
+        Container::new(2_000, 3_000)
     }
 }
 
@@ -27,10 +39,22 @@ impl ItemView for Cloak {
 
 impl ItemInteract for Cloak {
     fn mass(&self) -> u32 {
-        300
+        300 + self.pockets.mass()
+    }
+
+    fn volume(&self) -> u32 {
+        1_000 + self.pockets.volume()
+    }
+
+    fn container(&self) -> Option<&Container> {
+        Some(&self.pockets)
+    }
+
+    fn container_mut(&mut self) -> Option<&mut Container> {
+        Some(&mut self.pockets)
     }
 
-    fn is_wearable(&self) -> bool {
-        true
+    fn wear_slot(&self) -> Option<WearSlot> {
+        Some(WearSlot::Shoulders)
     }
 }
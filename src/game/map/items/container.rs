@@ -0,0 +1,37 @@
+use super::super::item::{Item, ItemInteract};
+
+/// Space inside an item (a sack, pockets) where other items could be put
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Container {
+    #[serde(rename = "i")]
+    pub items: Vec<Item>,
+    /// in milliliters
+    #[serde(rename = "v")]
+    pub max_volume: u32,
+    /// in grams
+    #[serde(rename = "m")]
+    pub max_mass: u32,
+}
+
+impl Container {
+    pub fn new(max_volume: u32, max_mass: u32) -> Self {
+        Self {
+            items: Vec::new(),
+            max_volume,
+            max_mass,
+        }
+    }
+
+    pub fn volume(&self) -> u32 {
+        self.items.iter().map(ItemInteract::volume).sum()
+    }
+
+    pub fn mass(&self) -> u32 {
+        self.items.iter().map(ItemInteract::mass).sum()
+    }
+
+    pub fn fits(&self, item: &Item) -> bool {
+        self.volume() + item.volume() <= self.max_volume
+            && self.mass() + item.mass() <= self.max_mass
+    }
+}
@@ -1,29 +1,51 @@
 use geometry::Point;
 
-use super::super::{
-    super::{bodies::Body, human::Personality},
-    item::{ItemInteract, ItemView},
+use super::{
+    super::{
+        super::{animals::AnimalIdentity, bodies::Body, human::Personality},
+        item::{ItemInteract, ItemView},
+    },
+    BodyPart, BodyPartType,
 };
 
+/// Whose corpse it is
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Identity {
+    Human(Personality),
+    Animal(AnimalIdentity),
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Corpse {
-    pub character: Personality,
+    pub character: Identity,
     pub body: Body,
 }
 
 impl Corpse {
     pub fn new(character: Personality, body: Body) -> Self {
-        Self { character, body }
+        Self {
+            character: Identity::Human(character),
+            body,
+        }
+    }
+
+    pub fn animal(identity: AnimalIdentity, body: Body) -> Self {
+        Self {
+            character: Identity::Animal(identity),
+            body,
+        }
     }
 }
 
 impl ItemView for Corpse {
     fn name(&self) -> String {
         let mut adjectives = Vec::new();
-        if self.body.wear.is_empty() {
+        if matches!(self.character, Identity::Human(..)) && self.body.wear.is_empty() {
             adjectives.push("naked");
         }
         let age_name = if let Some(bp) = self.body.parts.get(&Point::new(0, 0)) {
+            adjectives.append(&mut mutilations(bp));
             adjectives.push(bp.freshness().adjective());
             bp.age_name()
         } else {
@@ -37,9 +59,42 @@ impl ItemView for Corpse {
     }
 }
 
+/// Adjectives describing which limbs were cut off from the torso
+fn mutilations(torso: &BodyPart) -> Vec<&'static str> {
+    let (mut heads, mut arms, mut legs) = (0, 0, 0);
+    for bp in &torso.outside {
+        match bp.typ {
+            BodyPartType::HumanHead(..) | BodyPartType::DogHead(..) => heads += 1,
+            BodyPartType::HumanLeftArm(..) | BodyPartType::HumanRightArm(..) => arms += 1,
+            BodyPartType::HumanLeftLeg(..)
+            | BodyPartType::HumanRightLeg(..)
+            | BodyPartType::DogPaw(..) => legs += 1,
+            _ => {}
+        }
+    }
+    let mut adjectives = Vec::new();
+    if heads == 0 {
+        adjectives.push("headless");
+    }
+    if torso.is_human() {
+        match arms {
+            0 => adjectives.push("armless"),
+            1 => adjectives.push("one-armed"),
+            _ => {}
+        }
+    }
+    match (torso.is_human(), legs) {
+        (_, 0) => adjectives.push("legless"),
+        (_, 1) => adjectives.push("one-legged"),
+        (false, 2) => adjectives.push("two-legged"),
+        (false, 3) => adjectives.push("three-legged"),
+        _ => {}
+    }
+    adjectives
+}
+
 impl ItemInteract for Corpse {
     fn mass(&self) -> u32 {
-        // TODO: return bodies mass
-        60_000
+        self.body.mass()
     }
 }
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
-use super::super::item::{ItemInteract, ItemTag, ItemView};
+use super::super::{
+    super::bodies::{Damage, DamageType},
+    item::{ItemInteract, ItemTag, ItemView},
+};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Knife {}
@@ -35,4 +38,12 @@ impl ItemInteract for Knife {
     fn mass(&self) -> u32 {
         500
     }
+
+    fn butch_speed(&self) -> f64 {
+        10.0
+    }
+
+    fn melee_damage(&self) -> Option<Damage> {
+        Some(Damage::new(DamageType::Pierce, 12))
+    }
 }
@@ -1,19 +1,23 @@
 pub use axe::Axe;
 pub use body_part::{BodyPart, BodyPartType};
 pub use cloak::Cloak;
-pub use corpse::Corpse;
+pub use container::Container;
+pub use corpse::{Corpse, Identity};
 pub use gravestone::Gravestone;
 pub use hat::Hat;
 pub use knife::Knife;
 pub use rags::Rags;
+pub use sack::Sack;
 pub use shovel::Shovel;
 
 mod axe;
 mod body_part;
 mod cloak;
+mod container;
 mod corpse;
 mod gravestone;
 mod hat;
 mod knife;
 mod rags;
+mod sack;
 mod shovel;
@@ -1,4 +1,4 @@
-use super::super::item::{ItemInteract, ItemView};
+use super::super::item::{ItemInteract, ItemView, WearSlot};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Rags {}
@@ -30,7 +30,7 @@ impl ItemInteract for Rags {
         300
     }
 
-    fn is_wearable(&self) -> bool {
-        true
+    fn wear_slot(&self) -> Option<WearSlot> {
+        Some(WearSlot::Torso)
     }
 }
@@ -0,0 +1,57 @@
+use super::{
+    super::item::{ItemInteract, ItemView},
+    Container,
+};
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Sack {
+    #[serde(rename = "c")]
+    pub container: Container,
+}
+
+impl Sack {
+    pub fn new() -> Self {
+        Self {
+            // big enough for a corpse
+            container: Container::new(100_000, 100_000),
+        }
+    }
+}
+
+impl Default for Sack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ItemView for Sack {
+    fn name(&self) -> String {
+        if self.container.items.is_empty() {
+            "empty sack".to_string()
+        } else {
+            "sack".to_string()
+        }
+    }
+
+    fn looks_like(&self) -> &'static str {
+        "sack"
+    }
+}
+
+impl ItemInteract for Sack {
+    fn mass(&self) -> u32 {
+        500 + self.container.mass()
+    }
+
+    fn volume(&self) -> u32 {
+        2_000 + self.container.volume()
+    }
+
+    fn container(&self) -> Option<&Container> {
+        Some(&self.container)
+    }
+
+    fn container_mut(&mut self) -> Option<&mut Container> {
+        Some(&mut self.container)
+    }
+}
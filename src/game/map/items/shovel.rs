@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
-use super::super::item::{ItemInteract, ItemTag, ItemView};
+use super::super::{
+    super::bodies::{Damage, DamageType},
+    item::{ItemInteract, ItemTag, ItemView},
+};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Shovel {}
@@ -35,4 +38,8 @@ impl ItemInteract for Shovel {
     fn mass(&self) -> u32 {
         2_000 // 2 kg (probably it's a very small shovel)
     }
+
+    fn melee_damage(&self) -> Option<Damage> {
+        Some(Damage::new(DamageType::Blunt, 15))
+    }
 }
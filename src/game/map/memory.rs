@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::{item::ItemView, terrain::TerrainView, Terrain, Tile};
+
+/// How the tile looked when the player saw it last time, units are not remembered because they move
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TileView {
+    /// Terrain as it was, it also tells if the tile could be walked through
+    #[serde(rename = "t")]
+    pub terrain: Terrain,
+    /// Names of items, the top one goes last
+    #[serde(rename = "n")]
+    pub items: Vec<String>,
+    /// Sprite of the top item, only it is drawn
+    #[serde(rename = "s")]
+    pub top_sprite: Option<String>,
+}
+
+impl TileView {
+    pub fn new(tile: &Tile) -> Self {
+        Self {
+            terrain: tile.terrain.clone(),
+            items: tile.items.iter().map(ItemView::name).collect(),
+            top_sprite: tile.top_item().map(|i| i.looks_like().to_string()),
+        }
+    }
+
+    pub fn top_sprite(&self) -> Option<&str> {
+        self.top_sprite.as_deref()
+    }
+
+    /// There is no need to remember the tile again if it looks the same
+    pub fn looks_same(&self, other: &Self) -> bool {
+        self.terrain.looks_like() == other.terrain.looks_like()
+            && self.terrain.name() == other.terrain.name()
+            && self.items == other.items
+            && self.top_sprite == other.top_sprite
+    }
+}
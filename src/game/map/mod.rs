@@ -1,19 +1,29 @@
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 use geometry::Point;
+use rand::Rng;
 
 pub use chunk::Chunk;
 pub use item::{Item, ItemInteract, ItemTag, ItemView};
+pub use memory::TileView;
 pub use passage::Passage;
 pub use pos::{ChunkPos, TilePos};
 pub use terrain::{Terrain, TerrainInteract, TerrainView};
 pub use tile::Tile;
 
-use crate::fov::FovMap;
+use crate::{
+    fov::FovMap,
+    pathfinding::PathMap,
+    savefile::{self, LoadError, RegionPos, SaveError},
+};
+
+use super::Avatar;
 
 pub mod chunk;
 pub mod item;
 pub mod items;
+mod memory;
 pub mod passage;
 pub mod pos;
 pub mod terrain;
@@ -23,23 +33,281 @@ pub mod tile;
 pub struct Map {
     pub seed: String,
     pub chunks: HashMap<ChunkPos, Chunk>,
+    /// Chunks changed since the last save
     pub changed: HashSet<ChunkPos>,
+    /// Units born with newly generated chunks, waiting to be added to the world
+    pub spawns: Vec<Avatar>,
+    /// Directory with region files of the saved world
+    regions: PathBuf,
+    /// Regions already read from their files
+    loaded_regions: HashSet<RegionPos>,
+    /// Chunks that are saved in region files
+    stored: HashSet<ChunkPos>,
+    /// Broken chunks found in region files, they were generated again and the player is told so
+    pub lost: Vec<LoadError>,
+    /// Units standing in chunks that are not in memory, as tile indexes and unit ids
+    unplaced: HashMap<ChunkPos, Vec<(usize, usize)>>,
+    /// Unloaded regions with changed chunks written aside, they wait for the next save
+    /// because region files are written only together with the rest of the world
+    unloaded: HashSet<RegionPos>,
+    /// Regions read from a broken file or from a backup, the next save writes them again
+    outdated: HashSet<RegionPos>,
+    /// Number of the last save, region files written later are left from an unfinished save
+    saved: u64,
 }
 
 impl Map {
+    /// Chunks not in `chunks` are read from `regions` when needed or generated from the seed
+    pub fn new(
+        seed: String,
+        chunks: HashMap<ChunkPos, Chunk>,
+        regions: PathBuf,
+        saved: u64,
+    ) -> Self {
+        Self {
+            seed,
+            changed: chunks.keys().copied().collect(),
+            chunks,
+            spawns: Vec::new(),
+            regions,
+            loaded_regions: HashSet::new(),
+            stored: HashSet::new(),
+            lost: Vec::new(),
+            unplaced: HashMap::new(),
+            unloaded: HashSet::new(),
+            outdated: HashSet::new(),
+            saved,
+        }
+    }
+
     pub fn get_chunk(&mut self, pos: ChunkPos) -> &Chunk {
-        let seed = self.seed.clone();
-        self.chunks
-            .entry(pos)
-            .or_insert_with_key(|pos| Chunk::generate(seed, *pos))
+        self.get_or_generate(pos)
     }
 
     pub fn get_chunk_mut(&mut self, pos: ChunkPos) -> &mut Chunk {
-        let seed = self.seed.clone();
         self.changed.insert(pos);
-        self.chunks
-            .entry(pos)
-            .or_insert_with_key(|pos| Chunk::generate(seed, *pos))
+        self.get_or_generate(pos)
+    }
+
+    fn get_or_generate(&mut self, pos: ChunkPos) -> &mut Chunk {
+        if self.existing_chunk(pos).is_none() {
+            let (chunk, mut units) = Chunk::generate(self.seed.clone(), pos);
+            self.spawns.append(&mut units);
+            self.insert_chunk(chunk);
+        }
+        self.chunks.get_mut(&pos).unwrap()
+    }
+
+    /// Chunk from memory or from region files, it's not generated if it doesn't exist yet
+    fn existing_chunk(&mut self, pos: ChunkPos) -> Option<&Chunk> {
+        if !self.chunks.contains_key(&pos) {
+            self.load_region(pos.into());
+        }
+        self.chunks.get(&pos)
+    }
+
+    /// Units waiting for the chunk are put on its tiles
+    fn insert_chunk(&mut self, mut chunk: Chunk) {
+        for (i, unit_id) in self.unplaced.remove(&chunk.pos).unwrap_or_default() {
+            chunk.tiles[i].units.insert(unit_id);
+        }
+        self.chunks.insert(chunk.pos, chunk);
+    }
+
+    /// Walking unit comes to the tile, the chunk is changed only if something was trampled
+    pub fn step_on<R: Rng + ?Sized>(&mut self, unit_id: usize, pos: Point, rng: &mut R) {
+        let (chunk, i) = pos.to_chunk();
+        if self.get_or_generate(chunk).tiles[i].on_step(unit_id, rng) {
+            self.changed.insert(chunk);
+        }
+    }
+
+    /// The chunk is changed only if there was living grass on the tile
+    pub fn kill_grass(&mut self, pos: Point) {
+        let (chunk, i) = pos.to_chunk();
+        if self.get_or_generate(chunk).tiles[i].kill_grass() {
+            self.changed.insert(chunk);
+        }
+    }
+
+    /// Takes the unit away from the tile, positions of units are not saved with chunks
+    pub fn remove_unit(&mut self, unit_id: usize, pos: Point) {
+        let (chunk, i) = pos.to_chunk();
+        if let Some(chunk) = self.chunks.get_mut(&chunk) {
+            chunk.tiles[i].off_step(unit_id);
+        } else if let Some(unplaced) = self.unplaced.get_mut(&chunk) {
+            unplaced.retain(|&u| u != (i, unit_id));
+        }
+    }
+
+    /// Puts the unit on the tile, the chunk is not loaded for this
+    pub fn place_unit(&mut self, unit_id: usize, pos: Point) {
+        let (chunk, i) = pos.to_chunk();
+        if let Some(chunk) = self.chunks.get_mut(&chunk) {
+            chunk.tiles[i].units.insert(unit_id);
+        } else {
+            self.unplaced.entry(chunk).or_default().push((i, unit_id));
+        }
+    }
+
+    /// Reads the region file once, chunks already in memory or changed before the region
+    /// was unloaded are newer than saved ones
+    pub fn load_region(&mut self, region: RegionPos) {
+        if !self.loaded_regions.insert(region) {
+            return;
+        }
+        let mut data = savefile::load_region(&self.regions, region, self.saved);
+        if data.outdated {
+            self.outdated.insert(region);
+        }
+        let mut chunks = data.chunks;
+        for chunk in &chunks {
+            self.stored.insert(chunk.pos);
+        }
+        if self.unloaded.contains(&region) {
+            let dir = savefile::unsaved_dir(&self.regions);
+            let mut unsaved = savefile::load_region(&dir, region, self.saved);
+            chunks.retain(|chunk| !unsaved.chunks.iter().any(|c| c.pos == chunk.pos));
+            for chunk in &unsaved.chunks {
+                self.changed.insert(chunk.pos);
+            }
+            chunks.append(&mut unsaved.chunks);
+            data.lost.append(&mut unsaved.lost);
+        }
+        for chunk in chunks {
+            if !self.chunks.contains_key(&chunk.pos) {
+                self.insert_chunk(chunk);
+            }
+        }
+        self.lost.append(&mut data.lost);
+    }
+
+    /// Serialized regions with chunks changed since the last save, every one starts with `header`
+    pub fn changed_regions(&mut self, header: &str) -> Result<Vec<(RegionPos, String)>, SaveError> {
+        let regions: HashSet<RegionPos> = self
+            .changed
+            .iter()
+            .map(|&pos| RegionPos::from(pos))
+            .chain(self.unloaded.iter().copied())
+            .chain(self.outdated.iter().copied())
+            .collect();
+        let mut data = Vec::with_capacity(regions.len());
+        for region in regions {
+            data.push((region, self.region_data(region, header)?));
+        }
+        Ok(data)
+    }
+
+    /// Saved and changed chunks of the region, unchanged generated ones are not needed
+    fn region_data(&mut self, region: RegionPos, header: &str) -> Result<String, SaveError> {
+        // chunks of the region that are not in memory yet would be lost otherwise
+        self.load_region(region);
+        let chunks = self.chunks.values().filter(|chunk| {
+            RegionPos::from(chunk.pos) == region
+                && (self.stored.contains(&chunk.pos) || self.changed.contains(&chunk.pos))
+        });
+        savefile::region_data(chunks, header)
+    }
+
+    /// Drops regions out of `keep` from memory, they will be read or generated again when needed.
+    /// Changed chunks are written aside until the next save, `tick` is the current tick.
+    /// Regions that can't be written stay in memory.
+    pub fn unload_regions(
+        &mut self,
+        keep: &HashSet<RegionPos>,
+        tick: u128,
+    ) -> Result<(), SaveError> {
+        let far: HashSet<RegionPos> = self
+            .chunks
+            .keys()
+            .map(|&pos| RegionPos::from(pos))
+            .filter(|region| !keep.contains(region))
+            .collect();
+        let mut result = Ok(());
+        for region in far {
+            if let Err(error) = self.save_unloaded(region, tick) {
+                result = Err(error);
+                continue;
+            }
+            let positions: Vec<ChunkPos> = self
+                .chunks
+                .keys()
+                .copied()
+                .filter(|&pos| RegionPos::from(pos) == region)
+                .collect();
+            for pos in positions {
+                let mut chunk = self.chunks.remove(&pos).unwrap();
+                // units are not saved with chunks, they wait for the chunk to be loaded again
+                for (i, tile) in chunk.tiles.iter_mut().enumerate() {
+                    for unit_id in tile.units.drain() {
+                        self.unplaced.entry(pos).or_default().push((i, unit_id));
+                    }
+                }
+                self.changed.remove(&pos);
+            }
+            self.loaded_regions.remove(&region);
+        }
+        result
+    }
+
+    /// Writes changed chunks of the region aside, all of them are in memory
+    /// because the region was read when they were changed
+    fn save_unloaded(&mut self, region: RegionPos, tick: u128) -> Result<(), SaveError> {
+        let mut chunks = self
+            .chunks
+            .values()
+            .filter(|chunk| {
+                RegionPos::from(chunk.pos) == region && self.changed.contains(&chunk.pos)
+            })
+            .peekable();
+        if chunks.peek().is_none() {
+            return Ok(());
+        }
+        let header = savefile::header(tick, self.saved)?;
+        let data = savefile::region_data(chunks, header.as_str())?;
+        savefile::save_unloaded(&self.regions, region, data.as_str())?;
+        self.unloaded.insert(region);
+        Ok(())
+    }
+
+    /// Rots organic items in the chunk, it's marked as changed only if something has rotted
+    pub fn decay_chunk(&mut self, pos: ChunkPos, current_tick: u128) {
+        if self.get_or_generate(pos).decay(current_tick) {
+            self.changed.insert(pos);
+        }
+    }
+
+    /// Calls after the world is saved, `save` is the number of the save
+    pub fn mark_saved(&mut self, save: u64) {
+        self.stored.extend(self.changed.drain());
+        if !self.unloaded.is_empty() {
+            self.unloaded.clear();
+            savefile::clear_unloaded(&self.regions);
+        }
+        self.outdated.clear();
+        self.saved = save;
+    }
+
+    /// Remembers how the tile looks now, the chunk is changed only if it looks different
+    pub fn remember(&mut self, pos: Point) {
+        let (chunk, i) = pos.to_chunk();
+        let tile = &mut self.get_or_generate(chunk).tiles[i];
+        let view = TileView::new(tile);
+        if tile
+            .memory
+            .as_ref()
+            .map_or(true, |old| !old.looks_same(&view))
+        {
+            tile.memory = Some(Box::new(view));
+            self.changed.insert(chunk);
+        }
+    }
+
+    /// How the tile looked when the player saw it last time, unseen chunks are not generated
+    pub fn remembered(&mut self, pos: Point) -> Option<&TileView> {
+        let (chunk, i) = pos.to_chunk();
+        self.existing_chunk(chunk)
+            .and_then(|chunk| chunk.tiles[i].memory.as_deref())
     }
 
     pub fn get_tile(&mut self, pos: Point) -> &Tile {
@@ -93,3 +361,51 @@ impl FovMap for Map {
             .map_or(true, |c| c.tiles[pos].terrain.is_transparent())
     }
 }
+
+/// Map as a walking unit sees it, tiles occupied by the unit itself are not obstacles
+pub struct WalkerMap<'a> {
+    map: &'a mut Map,
+    unit_id: usize,
+    /// Tiles the walker sees, None if the walker knows the whole map
+    visible: Option<&'a HashSet<Point>>,
+}
+
+impl<'a> WalkerMap<'a> {
+    pub fn new(map: &'a mut Map, unit_id: usize) -> Self {
+        Self {
+            map,
+            unit_id,
+            visible: None,
+        }
+    }
+
+    /// Walker goes only through visible and remembered tiles, unknown ones are never generated
+    pub fn known(mut self, visible: &'a HashSet<Point>) -> Self {
+        self.visible = Some(visible);
+        self
+    }
+}
+
+impl PathMap for WalkerMap<'_> {
+    fn passage_cost(&mut self, pos: Point) -> Option<f32> {
+        let passage = if self.visible.map_or(true, |visible| visible.contains(&pos)) {
+            let tile = self.map.get_tile(pos);
+            if tile.units.iter().any(|&i| i != self.unit_id) {
+                return None;
+            }
+            tile.terrain.passage()
+        } else {
+            // units out of sight are unknown, only the terrain is remembered
+            self.map.remembered(pos)?.terrain.passage()
+        };
+        match passage {
+            Passage::Passable(ticks) => Some(ticks),
+            Passage::Impassable => None,
+        }
+    }
+
+    fn min_cost(&self) -> f32 {
+        // dirt is the easiest terrain to walk through
+        10.0
+    }
+}
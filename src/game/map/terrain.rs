@@ -46,6 +46,10 @@ pub trait TerrainInteract {
     fn read(&self) -> String {
         unreachable!()
     }
+    /// multiplier of decay speed for organic items lying here
+    fn decay_rate(&self) -> f64 {
+        1.0
+    }
 }
 
 #[cfg(test)]
@@ -15,6 +15,11 @@ impl Dirt {
     pub fn new(variant: DirtVariant) -> Self {
         Self { variant }
     }
+
+    /// Trampled dirt can't be trampled more
+    pub fn is_flat(&self) -> bool {
+        matches!(self.variant, DirtVariant::Flat)
+    }
 }
 
 impl Default for Dirt {
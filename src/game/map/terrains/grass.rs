@@ -116,6 +116,11 @@ impl TerrainInteract for Grass {
     fn dig_result(&self) -> (Terrain, Vec<Item>) {
         (Pit::new().into(), vec![])
     }
+
+    fn decay_rate(&self) -> f64 {
+        // insects and moisture
+        1.5
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone)]
@@ -33,4 +33,9 @@ impl TerrainInteract for Pit {
     fn passage(&self) -> Passage {
         Passage::Impassable
     }
+
+    fn decay_rate(&self) -> f64 {
+        // half-buried things rot slower
+        0.5
+    }
 }
@@ -5,9 +5,11 @@ use std::collections::HashSet;
 use rand::Rng;
 
 use super::{
+    super::bodies::Decay,
     item::{Item, ItemInteract},
     terrain::{Terrain, TerrainInteract},
     terrains::{Dirt, DirtVariant},
+    TileView,
 };
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -16,9 +18,18 @@ pub struct Tile {
     pub terrain: Terrain,
     #[serde(rename = "i")]
     pub items: Vec<Item>,
-    #[serde(default)]
-    #[serde(rename = "u")]
+    /// Units standing here, not saved: they are placed again from the list of units
+    #[serde(skip)]
     pub units: HashSet<usize>,
+    /// Ticks already spent on digging here
+    #[serde(default)]
+    #[serde(rename = "d")]
+    pub dug: u32,
+    /// How the player saw the tile last time, it's drawn when the tile is out of sight
+    #[serde(default)]
+    #[serde(rename = "m")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Box<TileView>>,
 }
 
 impl Tile {
@@ -27,6 +38,8 @@ impl Tile {
             terrain,
             items: Vec::new(),
             units: HashSet::new(),
+            dug: 0,
+            memory: None,
         }
     }
 
@@ -35,29 +48,58 @@ impl Tile {
         self.units.remove(&unit_id);
     }
 
-    /// Calls when avatar walks on tile
-    pub fn on_step(&mut self, unit_id: usize) {
+    /// Calls when avatar walks on tile, returns true if the terrain was trampled
+    pub fn on_step<R: Rng + ?Sized>(&mut self, unit_id: usize, rng: &mut R) -> bool {
         self.units.insert(unit_id);
         // TODO: (for future) footprints
-        if rand::thread_rng().gen_bool(0.1) {
+        if rng.gen_bool(0.1) {
             match self.terrain {
                 Terrain::Grass(..) => {
-                    self.terrain = Dirt::new(rand::random::<DirtVariant>()).into();
+                    self.terrain = Dirt::new(rng.gen::<DirtVariant>()).into();
+                    return true;
                 }
-                Terrain::Dirt(..) => {
+                Terrain::Dirt(ref dirt) if !dirt.is_flat() => {
                     self.terrain = Dirt::new(DirtVariant::Flat).into();
+                    return true;
                 }
                 _ => {}
             }
         }
+        false
     }
 
-    pub fn kill_grass(&mut self) {
-        if let Terrain::Grass(grass) = &mut self.terrain {
-            grass.die();
+    /// Returns true if living grass died
+    pub fn kill_grass(&mut self) -> bool {
+        match &mut self.terrain {
+            Terrain::Grass(grass) if !grass.dead() => {
+                grass.die();
+                true
+            }
+            _ => false,
         }
     }
 
+    /// Rots organic items lying here, taking the terrain into account.
+    /// Returns true if something became less fresh or rotted away,
+    /// growing counters of decay ticks are simulated again if the chunk isn't saved.
+    pub fn decay(&mut self, ticks: u32) -> bool {
+        let ticks = (f64::from(ticks) * self.terrain.decay_rate()).round() as u32;
+        let mut changed = false;
+        self.items.retain_mut(|item| match item {
+            Item::BodyPart(part) => {
+                let decay = part.decay(ticks);
+                changed |= decay != Decay::Same;
+                decay != Decay::RottedAway
+            }
+            Item::Corpse(corpse) => {
+                changed |= corpse.body.decay(ticks);
+                true
+            }
+            _ => true,
+        });
+        changed
+    }
+
     pub fn top_item(&self) -> Option<&Item> {
         self.items.last()
     }
@@ -65,6 +107,7 @@ impl Tile {
     pub fn dig(&mut self) -> Vec<Item> {
         let (terrain, items) = self.terrain.dig_result();
         self.terrain = terrain;
+        self.dug = 0;
         items
     }
 
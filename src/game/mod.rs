@@ -2,7 +2,7 @@ pub use actions::{Action, ActionType};
 pub use avatar::Avatar;
 pub use fov::Fov;
 pub use game_data::GameData;
-pub use log::Log;
+pub use log::{Log, LogCategory, LogEvent};
 pub use map::{
     pos::{ChunkPos, TilePos},
     Chunk, Item, ItemInteract, ItemTag, ItemView, Map, Terrain, TerrainInteract, TerrainView, Tile,
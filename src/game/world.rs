@@ -3,32 +3,62 @@ use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 
 use geometry::{circles, Direction, Point, TwoDimDirection};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
 
 use crate::{
     fov::field_of_view_set,
-    savefile::{self, GameView, Meta, SaveError},
+    pathfinding::{find_path, Path},
+    savefile::{self, GameView, LoadError, Meta, RegionPos, SaveError, WorldData},
 };
 
 use super::{
     ai::Brain,
     avatar::Soul,
-    map::{item::ItemView, terrain::TerrainView},
-    Action, Avatar, Chunk, ChunkPos, Fov, Log, Map, TilePos,
+    bodies::{Body, BodyPartPath, Damage},
+    log::{LogCategory, LogEvent},
+    map::{item::ItemView, items::Corpse, terrain::TerrainView, TileView, WalkerMap},
+    Action, ActionType, Avatar, Chunk, ChunkPos, Fov, Item, Log, Map, TilePos,
 };
 
 // TODO: weather and outside lighting system
 const VISION_RANGE: i32 = 64;
+const HEARING_RANGE: i32 = 24;
+/// How often organic stuff around the player decays
+const DECAY_INTERVAL: u32 = 60;
+/// Radius in chunks around the player where decay is simulated
+const DECAY_RADIUS: i32 = 2;
+/// How often chunks far from the player and active units are unloaded
+const UNLOAD_INTERVAL: u32 = 600;
+/// Radius in chunks around the player and active units where chunks stay in memory
+const KEEP_RADIUS: i32 = 4;
+
+/// Random generator that drives every random decision in the world, saved with the world
+pub type WorldRng = Pcg64;
+
+/// FNV-1a hash of the seed, `DefaultHasher` may change between Rust releases
+/// and the same seed would give another world
+pub fn seed_hash(seed: &str) -> u64 {
+    seed.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn seeded_rng(seed: &str) -> WorldRng {
+    WorldRng::seed_from_u64(seed_hash(seed))
+}
 
 pub struct World {
     pub meta: Meta,
     pub game_view: GameView,
     units: Vec<Avatar>, // TODO: move units to separate struct probably
     loaded_units: HashSet<usize>,
+    /// Strangers that player already saw, new ones interrupt player's actions
+    noticed_units: HashSet<usize>,
     map: RefCell<Map>,
     fov: Fov,
     log: RefCell<Log>,
-    // TODO: add Rng created with seed
+    rng: RefCell<WorldRng>,
     // TODO: add WorldLog
 }
 
@@ -40,102 +70,167 @@ impl World {
         units: Vec<Avatar>,
         chunks: HashMap<ChunkPos, Chunk>,
     ) -> Self {
-        let changed = chunks.keys().copied().collect();
         let loaded_units = HashSet::from([0]);
+        let rng = seeded_rng(meta.seed.as_str());
         let mut world = Self {
-            map: RefCell::new(Map {
-                seed: meta.seed.clone(),
+            map: RefCell::new(Map::new(
+                meta.seed.clone(),
                 chunks,
-                changed,
-            }),
+                savefile::regions_dir(&meta.path),
+                meta.saves,
+            )),
             meta,
             game_view,
             units,
             loaded_units,
+            noticed_units: HashSet::new(),
             fov: Fov::default(),
             log: RefCell::new(log),
+            rng: RefCell::new(rng),
         };
+        world.place_units();
         world.load_units();
         world.calc_fov();
+        world.noticed_units = world.visible_strangers();
         world
     }
 
+    /// Restores saved state of random generator
+    pub fn with_rng(mut self, rng: WorldRng) -> Self {
+        self.rng = RefCell::new(rng);
+        self
+    }
+
+    /// Positions of units are not saved with chunks, tiles get them from the list of units.
+    /// Ids could shift if some units were lost from a broken savefile.
+    fn place_units(&mut self) {
+        let mut map = self.map.borrow_mut();
+        for (i, unit) in self.units.iter_mut().enumerate() {
+            if let Some(action) = &mut unit.action {
+                action.owner = i;
+            }
+            if !unit.dead {
+                for pos in unit.occupied_tiles() {
+                    map.place_unit(i, pos);
+                }
+            }
+        }
+    }
+
     /// Calls one time after world is created
     pub fn init(mut self) -> Self {
         self.kill_grass(self.player().pos, 13, 0.8);
         self.units.iter().enumerate().for_each(|(i, unit)| {
-            self.map.borrow_mut().get_tile_mut(unit.pos).on_step(i);
+            for pos in unit.occupied_tiles() {
+                self.map
+                    .borrow_mut()
+                    .step_on(i, pos, &mut *self.rng.borrow_mut());
+            }
         });
         self
     }
 
+    /// Tiles going out of sight are remembered as they were seen last time
     pub fn calc_fov(&mut self) {
-        self.fov.set_visible(field_of_view_set(
-            self.player().pos,
-            VISION_RANGE,
+        let visible = field_of_view_set(
+            self.player().head_pos(),
+            self.player().body.capabilities().vision_range(VISION_RANGE),
             &self.map.borrow(),
-        ));
+        );
+        let mut map = self.map.borrow_mut();
+        for &pos in self.fov.visible().difference(&visible) {
+            map.remember(pos);
+        }
+        drop(map);
+        self.fov.set_visible(visible);
     }
 
-    // TODO: move this to savefile::save
-    fn make_data(&self) -> Result<String, SaveError> {
-        let mut data = serde_json::to_string(&self.meta).map_err(SaveError::from)?;
-        data.push('\n');
-        data.push_str(
+    fn make_data(&self) -> Result<WorldData, SaveError> {
+        let mut main = serde_json::to_string(&self.meta).map_err(SaveError::from)?;
+        main.push('\n');
+        main.push_str(
             serde_json::to_string(&self.game_view)
                 .map_err(SaveError::from)?
                 .as_str(),
         );
-        data.push('\n');
-        data.push_str(
-            serde_json::to_string(&self.log)
+        main.push('\n');
+        main.push_str(
+            serde_json::to_string(&self.rng)
                 .map_err(SaveError::from)?
                 .as_str(),
         );
-        for unit in &self.units {
-            data.push('\n');
-            data.push_str(
-                serde_json::to_string(unit)
-                    .map_err(SaveError::from)?
-                    .as_str(),
-            );
-        }
-        data.push_str("\n/units");
-        let mut map = self.map();
-        for coords in map.changed.clone() {
-            let chunk = map.get_chunk(coords);
-            data.push('\n');
-            data.push_str(
-                serde_json::to_string(chunk)
-                    .map_err(SaveError::from)?
-                    .as_str(),
-            );
-        }
-        data.push_str("\n/chunks");
+        let header = savefile::header(self.meta.current_tick, self.meta.saves)?;
+        let units = std::iter::once(Ok(header.clone()))
+            .chain(self.units.iter().map(serde_json::to_string))
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(SaveError::from)?
+            .join("\n");
+        let log = serde_json::to_string(&self.log).map_err(SaveError::from)?;
 
-        Ok(data)
+        Ok(WorldData {
+            main,
+            units,
+            log: format!("{}\n{}", header, log),
+            regions: self.map().changed_regions(header.as_str())?,
+        })
     }
 
-    pub fn save(&mut self) {
+    /// Keeps `backups` previous versions of the savefile, regions are written only if changed
+    pub fn save(&mut self, backups: usize) -> Result<(), SaveError> {
         self.meta.update_before_save();
-        savefile::save(
-            &self.meta.path,
-            self.make_data()
-                .expect("Error on preparing world data!")
-                .as_str(),
-        )
-        .map_err(|e| panic!("Error on saving world to {:?}: {:?}", self.meta.path, e))
-        .ok();
+        savefile::save(&self.meta.path, &self.make_data()?, backups)?;
+        self.map().mark_saved(self.meta.saves);
+        Ok(())
     }
 
     pub fn map(&self) -> RefMut<Map> {
         self.map.borrow_mut()
     }
 
+    pub fn rng(&self) -> RefMut<WorldRng> {
+        self.rng.borrow_mut()
+    }
+
     pub fn is_visible<P: Into<Point>>(&self, pos: P) -> bool {
         self.fov.visible().contains(&pos.into())
     }
 
+    /// How the tile looked when player saw it last time, None for visible tiles
+    pub fn remembered(&self, pos: Point) -> Option<TileView> {
+        if self.is_visible(pos) {
+            None
+        } else {
+            self.map().remembered(pos).cloned()
+        }
+    }
+
+    /// Player sees the tile or remembers it
+    pub fn is_known(&self, pos: Point) -> bool {
+        self.is_visible(pos) || self.map().remembered(pos).is_some()
+    }
+
+    /// Route of the player through visible and remembered tiles
+    pub fn player_route(&self, to: Point, budget: usize) -> Path {
+        find_path(
+            &mut WalkerMap::new(&mut self.map(), 0).known(self.fov.visible()),
+            self.player().pos,
+            to,
+            budget,
+        )
+    }
+
+    /// Player can see or hear what happens there
+    pub fn is_perceivable(&self, pos: Point) -> bool {
+        if self.is_visible(pos) {
+            return true;
+        }
+        let player = self.player();
+        let range = player.body.capabilities().hearing_range(HEARING_RANGE) as u32;
+        let dist = pos.square_distance(player.pos);
+        // nearby tiles could be felt even by blind and deaf
+        dist <= 2 || dist <= range * range
+    }
+
     pub fn get_unit(&self, unit_id: usize) -> &Avatar {
         self.units.get(unit_id).unwrap()
     }
@@ -144,6 +239,13 @@ impl World {
         self.units.get_mut(unit_id).unwrap()
     }
 
+    /// Units inside the bubble around the player, ordered by id
+    pub fn loaded_units(&self) -> impl Iterator<Item = (usize, &Avatar)> + '_ {
+        let mut ids: Vec<usize> = self.loaded_units.iter().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter().map(|i| (i, self.get_unit(i)))
+    }
+
     pub fn player(&self) -> &Avatar {
         self.get_unit(0)
     }
@@ -152,18 +254,109 @@ impl World {
         self.get_unit_mut(0)
     }
 
-    pub fn move_avatar(&mut self, unit_id: usize, dir: Direction) {
-        let mut pos = self.units.get(unit_id).unwrap().pos;
-        let (old_chunk, _) = pos.to_chunk();
-        self.map().get_tile_mut(pos).off_step(unit_id);
-        pos += dir;
-        if let Some(unit) = self.units.get_mut(unit_id) {
-            unit.pos = pos;
-            if let Ok(dir) = TwoDimDirection::try_from(dir) {
-                unit.vision = dir;
+    /// Zombie standing on the tile, they are preferred over corpses lying on the same tile,
+    /// the lowest id is taken if there are several
+    pub fn zombie_at(&self, pos: Point) -> Option<usize> {
+        let units: Vec<usize> = self.map().get_tile(pos).units.iter().copied().collect();
+        units
+            .into_iter()
+            .filter(|&i| self.get_unit(i).is_zombie())
+            .min()
+    }
+
+    /// Body of a zombie or a corpse at the position
+    pub fn inspect_body_at<T, F: FnOnce(Option<&Body>) -> T>(&self, pos: Point, f: F) -> T {
+        if let Some(unit_id) = self.zombie_at(pos) {
+            f(Some(&self.get_unit(unit_id).body))
+        } else {
+            let mut map = self.map();
+            f(map.get_tile(pos).items.iter().find_map(|i| match i {
+                Item::Corpse(corpse) => Some(&corpse.body),
+                _ => None,
+            }))
+        }
+    }
+
+    /// Same as `inspect_body_at` but allows changing the body,
+    /// the chunk is marked as changed only if there is a corpse
+    pub fn with_body_at<T, F: FnOnce(Option<&mut Body>) -> T>(&mut self, pos: Point, f: F) -> T {
+        if let Some(unit_id) = self.zombie_at(pos) {
+            f(Some(&mut self.get_unit_mut(unit_id).body))
+        } else {
+            let mut map = self.map();
+            let has_corpse = map
+                .get_tile(pos)
+                .items
+                .iter()
+                .any(|i| matches!(i, Item::Corpse(..)));
+            if !has_corpse {
+                return f(None);
             }
+            f(map
+                .get_tile_mut(pos)
+                .items
+                .iter_mut()
+                .find_map(|i| match i {
+                    Item::Corpse(corpse) => Some(&mut corpse.body),
+                    _ => None,
+                }))
         }
-        self.map().get_tile_mut(pos).on_step(unit_id);
+    }
+
+    /// Why the unit can't stand at `pos` facing `vision`
+    pub fn body_obstacle(
+        &self,
+        unit_id: usize,
+        pos: Point,
+        vision: TwoDimDirection,
+    ) -> Option<String> {
+        let tiles = Avatar::body_tiles(&self.get_unit(unit_id).body, pos, vision);
+        self.tiles_obstacle(&tiles, Some(unit_id))
+    }
+
+    /// Why a body can't occupy `tiles`,
+    /// tiles occupied by the unit `unit_id` itself are not obstacles
+    pub fn tiles_obstacle(&self, tiles: &[Point], unit_id: Option<usize>) -> Option<String> {
+        let mut map = self.map();
+        for &tile_pos in tiles {
+            let tile = map.get_tile(tile_pos);
+            if !tile.terrain.is_passable() {
+                return Some(format!("The {} is on the way", tile.terrain.name()));
+            }
+            if let Some(other) = tile.units.iter().copied().find(|&i| Some(i) != unit_id) {
+                return Some(format!(
+                    "{} is on the way",
+                    self.get_unit(other).name_for_actions()
+                ));
+            }
+        }
+        None
+    }
+
+    /// Turns the unit without moving, multitile bodies can be stuck
+    pub fn rotate_avatar(&mut self, unit_id: usize, vision: TwoDimDirection) -> bool {
+        let unit = self.get_unit(unit_id);
+        let pos = unit.pos;
+        if Avatar::body_tiles(&unit.body, pos, vision) == unit.occupied_tiles() {
+            self.get_unit_mut(unit_id).vision = vision;
+            return true;
+        }
+        if self.body_obstacle(unit_id, pos, vision).is_some() {
+            return false;
+        }
+        self.place_avatar(unit_id, pos, vision);
+        if unit_id == 0 {
+            self.calc_fov();
+        }
+        true
+    }
+
+    pub fn move_avatar(&mut self, unit_id: usize, dir: Direction) {
+        let unit = self.get_unit(unit_id);
+        let (old_chunk, _) = unit.pos.to_chunk();
+        let pos = unit.pos + dir;
+        let vision = TwoDimDirection::try_from(dir).unwrap_or(unit.vision);
+        self.place_avatar(unit_id, pos, vision);
         if unit_id == 0 && old_chunk != pos.to_chunk().0 {
             self.load_units();
         }
@@ -172,12 +365,31 @@ impl World {
         }
     }
 
+    /// Moves the unit from all its tiles to the new ones
+    fn place_avatar(&mut self, unit_id: usize, pos: Point, vision: TwoDimDirection) {
+        let unit = self.units.get_mut(unit_id).unwrap();
+        let old_tiles = unit.occupied_tiles();
+        unit.pos = pos;
+        unit.vision = vision;
+        let new_tiles = unit.occupied_tiles();
+        let mut map = self.map.borrow_mut();
+        for tile in old_tiles {
+            map.remove_unit(unit_id, tile);
+        }
+        for tile in new_tiles {
+            map.step_on(unit_id, tile, &mut *self.rng.borrow_mut());
+        }
+    }
+
     pub fn log(&self) -> RefMut<Log> {
         self.log.borrow_mut()
     }
 
     // TODO: move this somewhere else
     pub fn this_is(&self, pos: Point, multiline: bool) -> String {
+        if !self.is_visible(pos) {
+            return self.that_was(pos, multiline);
+        }
         let mut map = self.map();
         let tile = map.get_tile(pos);
         let mut this_is = format!("This is the {}", tile.terrain.name());
@@ -224,6 +436,29 @@ impl World {
         this_is
     }
 
+    /// Describes the tile out of sight by memory
+    fn that_was(&self, pos: Point, multiline: bool) -> String {
+        if let Some(view) = self.map().remembered(pos) {
+            let mut that_was = format!("You remember the {}", view.terrain.name());
+            if !view.items.is_empty() {
+                that_was.push(if multiline { '\n' } else { ' ' });
+                that_was.push_str("There was: ");
+                if multiline {
+                    that_was.push('\n');
+                }
+                let items: Vec<String> = view
+                    .items
+                    .iter()
+                    .map(|name| (if multiline { " - " } else { "" }).to_string() + name.as_str())
+                    .collect();
+                that_was += items.join(if multiline { "\n" } else { ", " }).as_str();
+            }
+            that_was
+        } else {
+            "You don't know what is there".to_string()
+        }
+    }
+
     pub fn kill_grass(&mut self, around: Point, diameter: u8, probability: f64) {
         for (dx, dy) in match diameter {
             7 => circles::CIRCLE7.iter().copied(),
@@ -235,9 +470,9 @@ impl World {
             let k = (1.0 - (dx as f64).hypot(dy as f64) / ((diameter - 1) as f64 / 2.0))
                 .min(1.0)
                 .max(0.0);
-            if rand::thread_rng().gen_bool(probability * k) {
+            if self.rng().gen_bool(probability * k) {
                 let pos = around + (dx, dy);
-                self.map().get_tile_mut(pos).kill_grass();
+                self.map().kill_grass(pos);
             }
         }
     }
@@ -252,25 +487,158 @@ impl World {
             .map(|u| u.action.as_ref().unwrap().clone())
             .collect();
         for action in actions {
+            // unit could be killed or interrupted by someone else's action
+            let owner = self.get_unit(action.owner);
+            if owner.dead || owner.action.is_none() {
+                continue;
+            }
             if action.finish >= self.meta.current_tick {
                 action.act(self);
             }
             if self.meta.current_tick == action.finish {
                 self.get_unit_mut(action.owner).action = None;
+                self.start_next_action(action.owner);
+            }
+        }
+    }
+
+    /// Starts the next queued action, the rest of the queue is cancelled if it is impossible
+    fn start_next_action(&mut self, unit_id: usize) {
+        let typ = if let Some(typ) = self.get_unit_mut(unit_id).action_queue.pop_front() {
+            typ
+        } else {
+            return;
+        };
+        match Action::new(unit_id, typ, self) {
+            Ok(action) => {
+                self.get_unit_mut(unit_id).action = Some(action.clone());
+                // starting in the same tick the previous one was finished
+                action.act(self);
+            }
+            Err(reason) => {
+                let unit = self.get_unit_mut(unit_id);
+                unit.action_queue.clear();
+                if unit_id == 0 {
+                    let pos = unit.pos;
+                    self.log()
+                        .push(LogEvent::new(reason, pos, LogCategory::Warning));
+                }
             }
         }
     }
 
+    /// Stops player's current action and everything queued after it
+    pub fn interrupt_player<S: Into<String>>(&mut self, reason: S) {
+        if let Some(action) = self.player().action.clone() {
+            action.cancel(self, reason.into());
+        }
+    }
+
+    /// Visible units that are not under player's control
+    fn visible_strangers(&self) -> HashSet<usize> {
+        self.loaded_units()
+            .filter(|(i, u)| *i != 0 && !u.dead && !u.is_zombie() && self.is_visible(u.pos))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn notice_units(&mut self) {
+        let visible = self.visible_strangers();
+        let new_unit = visible
+            .iter()
+            .copied()
+            .filter(|i| !self.noticed_units.contains(i))
+            .min();
+        self.noticed_units = visible;
+        if let Some(unit_id) = new_unit {
+            let name = self.get_unit(unit_id).name_as_object();
+            self.interrupt_player(format!("You see {}", name));
+        }
+    }
+
     pub fn add_unit(&mut self, unit: Avatar) -> usize {
-        let pos = unit.pos;
+        let tiles = unit.occupied_tiles();
         self.units.push(unit);
         self.load_units();
         let new_id = self.units.len() - 1;
-        self.map().get_tile_mut(pos).units.insert(new_id);
+        let mut map = self.map();
+        for pos in tiles {
+            map.place_unit(new_id, pos);
+        }
+        drop(map);
 
         new_id
     }
 
+    /// Deals damage to the unit's body part, destroyed limbs fall to the ground
+    pub fn damage_unit(&mut self, unit_id: usize, path: &BodyPartPath, damage: Damage) {
+        if unit_id == 0 {
+            self.interrupt_player("You are hurt!");
+        }
+        let result = self.units[unit_id]
+            .body
+            .damage(path, damage, &mut *self.rng.borrow_mut());
+        let unit = self.get_unit(unit_id);
+        let (pos, name) = (unit.pos, unit.name_possessive());
+        for part in result.damaged {
+            self.log().push(LogEvent::new(
+                format!("{} {} is {}", name, part, damage.typ.participle()),
+                pos,
+                LogCategory::Danger,
+            ));
+        }
+        for part in result.fallen {
+            self.log().push(LogEvent::new(
+                format!("{} {} falls off", name, part.name),
+                pos,
+                LogCategory::Danger,
+            ));
+            self.map().get_tile_mut(pos).items.push(part.into());
+        }
+        // living creatures can't survive without a brain
+        let unit = self.get_unit(unit_id);
+        let brainless = !unit.is_zombie() && !unit.body.capabilities().brain;
+        if result.fatal || brainless {
+            self.kill_unit(unit_id);
+        }
+    }
+
+    /// Unit stays in the list to keep ids of others, its remains lie on the ground
+    pub fn kill_unit(&mut self, unit_id: usize) {
+        let unit = self.get_unit_mut(unit_id);
+        unit.dead = true;
+        unit.action = None;
+        unit.action_queue.clear();
+        let pos = unit.pos;
+        let message = match unit.soul {
+            Soul::Player(..) => "You die".to_string(),
+            Soul::Zombie(..) | Soul::ZombieDog(..) => {
+                format!("{} is destroyed", unit.name_for_actions())
+            }
+            Soul::Villager(..) | Soul::Watchman(..) | Soul::Dog(..) => {
+                format!("{} dies", unit.name_for_actions())
+            }
+        };
+        let tiles = unit.occupied_tiles();
+        let body = std::mem::replace(&mut unit.body, Body::new(HashMap::new()));
+        let mut items: Vec<Item> = unit.wield.drain(..).collect();
+        if !body.parts.is_empty() {
+            if let Some(person) = unit.person() {
+                items.push(Corpse::new(person.clone(), body).into());
+            } else if let Some(identity) = unit.animal() {
+                items.push(Corpse::animal(identity.clone(), body).into());
+            }
+        }
+        let mut map = self.map();
+        for tile in tiles {
+            map.remove_unit(unit_id, tile);
+        }
+        map.get_tile_mut(pos).items.append(&mut items);
+        drop(map);
+        self.log()
+            .push(LogEvent::new(message, pos, LogCategory::Danger));
+    }
+
     fn load_units(&mut self) {
         self.loaded_units.clear();
         let center = self.player().pos;
@@ -285,10 +653,93 @@ impl World {
         }
     }
 
+    /// Rots zombies and organic items in chunks around the player,
+    /// other chunks will catch up when the player comes close
+    fn decay(&mut self) {
+        let current_tick = self.meta.current_tick;
+        let (center, _) = self.player().pos.to_chunk();
+        let mut map = self.map.borrow_mut();
+        for x in center.x - DECAY_RADIUS..=center.x + DECAY_RADIUS {
+            for y in center.y - DECAY_RADIUS..=center.y + DECAY_RADIUS {
+                map.decay_chunk(ChunkPos::new(x, y), current_tick);
+            }
+        }
+        drop(map);
+        for unit in &mut self.units {
+            if unit.is_zombie() && !unit.dead {
+                unit.body.decay(DECAY_INTERVAL);
+            }
+        }
+    }
+
+    /// Frees memory from regions far from the player and active units,
+    /// changed chunks there are written aside until the next save
+    fn unload_chunks(&mut self) {
+        let mut keep = HashSet::new();
+        for (_, unit) in self.loaded_units() {
+            let (center, _) = unit.pos.to_chunk();
+            for x in center.x - KEEP_RADIUS..=center.x + KEEP_RADIUS {
+                for y in center.y - KEEP_RADIUS..=center.y + KEEP_RADIUS {
+                    keep.insert(RegionPos::from(ChunkPos::new(x, y)));
+                }
+            }
+        }
+        let result = self.map().unload_regions(&keep, self.meta.current_tick);
+        if let Err(error) = result {
+            self.log().push(LogEvent::new(
+                format!("Can't unload the far part of the world: {}", error),
+                self.player().pos,
+                LogCategory::Warning,
+            ));
+        }
+    }
+
+    /// Brain is taken out of the unit for a while to let it look at the world
+    fn think<B: Brain + Default>(
+        &mut self,
+        unit_id: usize,
+        brain: fn(&mut Soul) -> Option<&mut B>,
+    ) -> Option<ActionType> {
+        let mut taken = std::mem::take(brain(&mut self.units[unit_id].soul)?);
+        taken.plan(unit_id, self);
+        let action = taken.action();
+        if let Some(b) = brain(&mut self.units[unit_id].soul) {
+            *b = taken;
+        }
+        action
+    }
+
+    /// Adds NPCs born with newly generated chunks
+    fn spawn_units(&mut self) {
+        let spawns = std::mem::take(&mut self.map().spawns);
+        for unit in spawns {
+            self.add_unit(unit);
+        }
+    }
+
+    /// Broken chunks read from region files while playing, they were generated again.
+    /// Errors are logged and returned to be shown to the player.
+    pub fn take_lost_chunks(&mut self) -> Vec<LoadError> {
+        let lost = std::mem::take(&mut self.map().lost);
+        let pos = self.player().pos;
+        for error in &lost {
+            self.log().push(LogEvent::new(
+                format!("Lost from the savefile: {}", error),
+                pos,
+                LogCategory::Warning,
+            ));
+        }
+        lost
+    }
+
     pub const BUBBLE_SQUARE_RADIUS: u32 = 128 * 128;
     pub const SPEND_LIMIT: u32 = 100; // TODO: probably it should be about 10-50
 
     pub fn tick(&mut self) {
+        // the world doesn't go on after the player's death
+        if self.player().dead {
+            return;
+        }
         self.act();
 
         let mut spend = 0;
@@ -297,21 +748,60 @@ impl World {
             spend += 1;
             self.act();
 
-            let mut unit_wants_actions = HashMap::new();
-            for (unit_id, unit) in self.units.iter_mut().skip(1).enumerate() {
-                if unit.action.is_none() {
-                    if let Soul::Zombie(_, brain) = &mut unit.soul {
-                        brain.plan();
-                        if let Some(action_type) = brain.action() {
-                            // +1 is because we skipped first one in enumeration
-                            unit_wants_actions.insert(unit_id + 1, action_type);
-                        }
-                    }
+            let mut unit_wants_actions = Vec::new();
+            self.spawn_units();
+            // units far from the player are frozen
+            let loaded: Vec<usize> = self
+                .loaded_units()
+                .map(|(i, _)| i)
+                .filter(|&i| i != 0)
+                .collect();
+            for unit_id in loaded {
+                let unit = &self.units[unit_id];
+                if unit.action.is_some() || unit.dead {
+                    continue;
+                }
+                // mindless zombies are slow to decide anything
+                if !unit.body.capabilities().brain && self.rng().gen_bool(0.5) {
+                    continue;
+                }
+                let action = match self.units[unit_id].soul {
+                    Soul::Player(..) => None,
+                    Soul::Zombie(..) => self.think(unit_id, |soul| match soul {
+                        Soul::Zombie(_, brain) => Some(brain),
+                        _ => None,
+                    }),
+                    Soul::Villager(..) => self.think(unit_id, |soul| match soul {
+                        Soul::Villager(_, brain) => Some(brain),
+                        _ => None,
+                    }),
+                    Soul::Watchman(..) => self.think(unit_id, |soul| match soul {
+                        Soul::Watchman(_, brain) => Some(brain),
+                        _ => None,
+                    }),
+                    Soul::Dog(..) => self.think(unit_id, |soul| match soul {
+                        Soul::Dog(_, brain) => Some(brain),
+                        _ => None,
+                    }),
+                    Soul::ZombieDog(..) => self.think(unit_id, |soul| match soul {
+                        Soul::ZombieDog(_, brain) => Some(brain),
+                        _ => None,
+                    }),
+                };
+                if let Some(action_type) = action {
+                    unit_wants_actions.push((unit_id, action_type));
                 }
             }
             for (unit_id, typ) in unit_wants_actions {
                 self.units.get_mut(unit_id).unwrap().action = Action::new(unit_id, typ, self).ok();
             }
+            self.notice_units();
+            if self.meta.current_tick % u128::from(DECAY_INTERVAL) == 0 {
+                self.decay();
+            }
+            if self.meta.current_tick % u128::from(UNLOAD_INTERVAL) == 0 {
+                self.unload_chunks();
+            }
             // self.kill_grass(self.player().pos, 13, 0.01);
         }
     }
@@ -320,21 +810,31 @@ impl World {
 #[cfg(test)]
 pub mod tests {
     use std::collections::HashMap;
+    use std::path::PathBuf;
 
     use geometry::Point;
+    use rand::Rng;
+
+    use crate::pathfinding::find_path;
 
     use super::{
         super::{
-            actions::implements::{Skip, Walk},
-            bodies::Freshness,
+            actions::implements::{Dig, Skip, Walk},
+            ai::Order,
+            animals::{helpers::dog_body, AnimalIdentity, FurColor},
+            bodies::{BodyPartPath, BodySize, Damage, DamageType, Freshness, Sex},
             human::{
-                helpers::human_body,
+                helpers::{human_body, human_centipede},
                 tests::personality::{dead_boy, tester_girl},
             },
-            map::terrains::{Boulder, BoulderSize, Dirt},
+            map::{
+                items::{Corpse, Sack, Shovel},
+                terrains::{Boulder, BoulderSize, Dirt, Grass, GrassVariant},
+                Item, ItemView, WalkerMap,
+            },
         },
-        savefile::{GameView, Meta},
-        Action, Avatar, Direction, Log, TerrainView, World,
+        savefile::{self, GameView, Meta},
+        seed_hash, Action, Avatar, ChunkPos, Direction, Log, Soul, TerrainView, TilePos, World,
     };
 
     pub fn prepare_world() -> World {
@@ -386,6 +886,27 @@ pub mod tests {
         assert_eq!(Point::new(2, 0), world.units.get(1).unwrap().pos)
     }
 
+    #[test]
+    pub fn test_same_seed_gives_same_result() {
+        let mut first = prepare_world();
+        let mut second = prepare_world();
+        for world in [&mut first, &mut second] {
+            add_zombie(world, Point::new(1, 0));
+            for _ in 0..50 {
+                world.player_mut().action = Some(Action::new(0, Skip {}.into(), world).unwrap());
+                world.tick();
+            }
+        }
+        assert_eq!(first.get_unit(1).pos, second.get_unit(1).pos);
+        assert_eq!(first.rng().gen::<u64>(), second.rng().gen::<u64>());
+    }
+
+    #[test]
+    pub fn test_seed_hash() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, seed_hash(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, seed_hash("a"));
+    }
+
     #[test]
     pub fn test_fov() {
         let mut world = prepare_world();
@@ -405,4 +926,626 @@ pub mod tests {
         assert!(world.is_visible(Point::new(2, 0)));
         assert!(!world.is_visible(Point::new(3, 0)));
     }
+
+    #[test]
+    pub fn test_map_memory() {
+        let mut world = prepare_world();
+        let pos = Point::new(1, 0);
+        world.map().get_tile_mut(pos).terrain = Dirt::default().into();
+        world.map().get_tile_mut(pos).items.clear();
+        world
+            .map()
+            .get_tile_mut(pos)
+            .items
+            .push(Shovel::new().into());
+        world.map().get_tile_mut(pos).items.push(Sack::new().into());
+        world.calc_fov();
+        assert!(world.remembered(pos).is_none());
+        assert!(world.this_is(pos, false).starts_with("This is the"));
+
+        let head = BodyPartPath::root(Point::new(0, 0)).child("head");
+        world.player_mut().body.detach(&head.child("left eye"));
+        world.player_mut().body.detach(&head.child("right eye"));
+        world.calc_fov();
+        world.map().get_tile_mut(pos).items.clear();
+        let view = world.remembered(pos).unwrap();
+        assert_eq!(vec!["shovel", "empty sack"], view.items);
+        assert_eq!(Some("sack"), view.top_sprite());
+        let this_is = world.this_is(pos, false);
+        assert!(this_is.starts_with("You remember the"));
+        assert!(this_is.ends_with("There was: shovel, empty sack"));
+        assert_eq!(
+            "You don't know what is there",
+            world.this_is(Point::new(100, 100), false)
+        );
+    }
+
+    #[test]
+    pub fn test_blindness() {
+        let mut world = prepare_world();
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Dirt::default().into();
+        world.calc_fov();
+        assert!(world.is_visible(Point::new(1, 0)));
+
+        let head = BodyPartPath::root(Point::new(0, 0)).child("head");
+        world.player_mut().body.detach(&head.child("left eye"));
+        world.player_mut().body.detach(&head.child("right eye"));
+        world.calc_fov();
+        assert!(world.is_visible(world.player().pos));
+        assert!(!world.is_visible(Point::new(1, 0)));
+        assert!(world.is_perceivable(Point::new(1, 0)));
+    }
+
+    #[test]
+    pub fn test_decay() {
+        let mut world = prepare_world();
+        let pos = Point::new(1, 0);
+        world.map().get_tile_mut(pos).terrain = Dirt::default().into();
+        world.map().get_tile_mut(pos).items.clear();
+        let character = dead_boy();
+        let body = human_body(&character, Freshness::Fresh);
+        let head = BodyPartPath::root(Point::new(0, 0)).child("head");
+        let eye = body.get_part(&head.child("left eye")).unwrap().clone();
+        world
+            .map()
+            .get_tile_mut(pos)
+            .items
+            .push(Corpse::new(character, body).into());
+        world.map().get_tile_mut(pos).items.push(eye.into());
+        world.decay();
+
+        world.meta.current_tick += u128::from(Freshness::Fresh.duration().unwrap());
+        world.decay();
+        {
+            let mut map = world.map();
+            let items = &map.get_tile(pos).items;
+            assert_eq!(2, items.len());
+            assert_eq!("naked rotten boy corpse", items[0].name());
+            assert!(matches!(
+                &items[1],
+                Item::BodyPart(part) if matches!(part.freshness(), Freshness::Rotten)
+            ));
+        }
+
+        world.meta.current_tick += u128::from(Freshness::Rotten.duration().unwrap());
+        world.decay();
+        let mut map = world.map();
+        let items = &map.get_tile(pos).items;
+        assert_eq!(1, items.len());
+        assert_eq!("naked skeletal boy corpse", items[0].name());
+        if let Item::Corpse(corpse) = &items[0] {
+            assert!(corpse.body.get_part(&head).is_some());
+            assert!(corpse.body.get_part(&head.child("brain")).is_none());
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[test]
+    pub fn test_decay_changes() {
+        let mut world = prepare_world();
+        let chunk = ChunkPos::new(0, 0);
+        let mut map = world.map();
+        for tile in map.get_chunk_mut(chunk).tiles.iter_mut() {
+            tile.items.clear();
+        }
+        map.changed.clear();
+        map.decay_chunk(chunk, 100);
+        assert!(!map.changed.contains(&chunk));
+
+        let character = dead_boy();
+        let body = human_body(&character, Freshness::Fresh);
+        let head = BodyPartPath::root(Point::new(0, 0)).child("head");
+        let eye = body.get_part(&head.child("left eye")).unwrap().clone();
+        map.chunks.get_mut(&chunk).unwrap().tiles[0]
+            .items
+            .push(eye.into());
+        // eye is still fresh, only the counter of decay ticks grows
+        map.decay_chunk(chunk, 200);
+        assert!(!map.changed.contains(&chunk));
+
+        let rotten = 200 + 2 * u128::from(Freshness::Fresh.duration().unwrap());
+        map.decay_chunk(chunk, rotten);
+        assert!(map.changed.contains(&chunk));
+    }
+
+    #[test]
+    pub fn test_walking_changes() {
+        let mut world = prepare_world();
+        for x in 1..=3 {
+            world.map().get_tile_mut(Point::new(x, 0)).terrain = Dirt::default().into();
+        }
+        let zombie = add_zombie(&mut world, Point::new(1, 0));
+        world.map().changed.clear();
+        // flat dirt can't be trampled, units are not saved with chunks
+        for _ in 0..10 {
+            world.move_avatar(zombie, Direction::East);
+            world.move_avatar(zombie, Direction::West);
+        }
+        assert!(world.map().changed.is_empty());
+        assert!(world
+            .map()
+            .get_tile(Point::new(1, 0))
+            .units
+            .contains(&zombie));
+    }
+
+    #[test]
+    pub fn test_looking_changes() {
+        let mut world = prepare_world();
+        let pos = Point::new(1, 0);
+        world.map().get_tile_mut(pos).terrain = Dirt::default().into();
+        world.map().get_tile_mut(pos).items.clear();
+        let (chunk, _) = pos.to_chunk();
+        world.map().changed.clear();
+        world.map().kill_grass(pos);
+        assert!(world.with_body_at(pos, |body| body.is_none()));
+        assert!(!world.map().changed.contains(&chunk));
+
+        world.map().get_tile_mut(pos).terrain = Grass::new(GrassVariant::Grass1).into();
+        world.map().changed.clear();
+        world.map().kill_grass(pos);
+        assert!(world.map().changed.contains(&chunk));
+        world.map().changed.clear();
+        world.map().kill_grass(pos);
+        assert!(!world.map().changed.contains(&chunk));
+    }
+
+    #[test]
+    pub fn test_unload_chunks() {
+        let mut meta = Meta::new("unload", "test");
+        meta.path = PathBuf::from("save/unload.save");
+        let path = meta.path.clone();
+        let mut world = World::new(
+            meta,
+            GameView::default(),
+            Log::new(),
+            vec![Avatar::player(tester_girl(), Point::new(0, 0))],
+            HashMap::new(),
+        );
+        let far = Point::new(1000, 0);
+        let (far_chunk, _) = far.to_chunk();
+        world
+            .map()
+            .get_tile_mut(far)
+            .items
+            .push(Shovel::new().into());
+        world.unload_chunks();
+        let regions = savefile::regions_dir(&path);
+        let unloaded = savefile::unsaved_dir(&regions);
+        {
+            let mut map = world.map();
+            // the changed chunk is not kept in memory
+            assert!(!map.chunks.contains_key(&far_chunk));
+            assert!(!map.changed.contains(&far_chunk));
+            assert!(map.chunks.contains_key(&ChunkPos::new(0, 0)));
+            // region files are written only by the save
+            assert!(unloaded.is_dir());
+            assert_eq!(1, regions.read_dir().unwrap().count());
+            assert!(matches!(
+                map.get_tile(far).items.last(),
+                Some(Item::Shovel(..))
+            ));
+            assert!(map.changed.contains(&far_chunk));
+        }
+
+        world.unload_chunks();
+        assert!(!world.map().chunks.contains_key(&far_chunk));
+        world.save(0).unwrap();
+        assert!(!unloaded.exists());
+        assert!(regions.read_dir().unwrap().count() > 0);
+        assert!(!world.map().changed.contains(&far_chunk));
+        world.unload_chunks();
+        assert!(!world.map().chunks.contains_key(&far_chunk));
+        assert!(matches!(
+            world.map().get_tile(far).items.last(),
+            Some(Item::Shovel(..))
+        ));
+        let world2 = savefile::load_world(&path).unwrap();
+        assert!(matches!(
+            world2.map().get_tile(far).items.last(),
+            Some(Item::Shovel(..))
+        ));
+        savefile::delete(&path);
+    }
+
+    #[test]
+    pub fn test_damage() {
+        let mut world = prepare_world();
+        let pos = Point::new(1, 0);
+        world.map().get_tile_mut(pos).items.clear();
+        let zombie = add_zombie(&mut world, pos);
+        let torso = BodyPartPath::root(Point::new(0, 0));
+
+        world.damage_unit(
+            zombie,
+            &torso.child("left arm"),
+            Damage::new(DamageType::Cut, 1),
+        );
+        assert!(world
+            .get_unit(zombie)
+            .body
+            .get_part(&torso.child("left arm"))
+            .is_some());
+
+        world.damage_unit(
+            zombie,
+            &torso.child("left arm"),
+            Damage::new(DamageType::Cut, 1000),
+        );
+        assert!(world
+            .get_unit(zombie)
+            .body
+            .get_part(&torso.child("left arm"))
+            .is_none());
+        assert!(!world.get_unit(zombie).dead);
+        assert!(matches!(
+            world.map().get_tile(pos).items.last(),
+            Some(Item::BodyPart(part)) if part.name == "left arm"
+        ));
+
+        world.damage_unit(
+            zombie,
+            &torso.child("head").child("brain"),
+            Damage::new(DamageType::Blunt, 1000),
+        );
+        assert!(world.get_unit(zombie).dead);
+        assert!(world.map().get_tile(pos).units.is_empty());
+        assert!(matches!(
+            world.map().get_tile(pos).items.last(),
+            Some(Item::Corpse(..))
+        ));
+    }
+
+    #[test]
+    pub fn test_player_death() {
+        let mut world = prepare_world();
+        let action = Action::new(0, Skip {}.into(), &world).unwrap();
+        world.player_mut().action = Some(action.clone());
+        world.kill_unit(0);
+        assert!(world.player().dead);
+        assert!(world.player().action.is_none());
+
+        // nothing happens after the death even if something is left to do
+        let tick = world.meta.current_tick;
+        world.player_mut().action = Some(action);
+        world.tick();
+        assert_eq!(tick, world.meta.current_tick);
+    }
+
+    #[test]
+    pub fn test_zombie_orders() {
+        let mut world = prepare_world();
+        for x in 1..=5 {
+            for y in -1..=1 {
+                let mut map = world.map();
+                let tile = map.get_tile_mut(Point::new(x, y));
+                tile.terrain = Dirt::default().into();
+                tile.items.clear();
+            }
+        }
+        let zombie = add_zombie(&mut world, Point::new(4, 0));
+
+        assert!(world.get_unit_mut(zombie).give_order(Order::Stay));
+        for _ in 0..100 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert_eq!(Point::new(4, 0), world.get_unit(zombie).pos);
+
+        assert!(world.get_unit_mut(zombie).give_order(Order::FollowMe));
+        for _ in 0..1000 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert!(
+            world
+                .get_unit(zombie)
+                .pos
+                .square_distance(world.player().pos)
+                <= 2
+        );
+        assert!(!world.player_mut().give_order(Order::Stay));
+    }
+
+    #[test]
+    pub fn test_impossible_order() {
+        let mut world = prepare_world();
+        for x in 1..=3 {
+            let mut map = world.map();
+            let tile = map.get_tile_mut(Point::new(x, 0));
+            tile.terrain = Dirt::default().into();
+            tile.items.clear();
+        }
+        let zombie = add_zombie(&mut world, Point::new(2, 0));
+        world.get_unit_mut(zombie).wield.clear();
+        assert!(world
+            .get_unit_mut(zombie)
+            .give_order(Order::Dig(Point::new(3, 0))));
+        for _ in 0..10 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert!(matches!(
+            &world.get_unit(zombie).soul,
+            Soul::Zombie(_, brain) if brain.order == Order::Stay
+        ));
+        assert!(world
+            .log()
+            .new_events()
+            .iter()
+            .any(|e| e.msg.contains("You need a shovel")));
+    }
+
+    #[test]
+    pub fn test_walker_path() {
+        let mut world = prepare_world();
+        for x in -1..=6 {
+            for y in -2..=2 {
+                let mut map = world.map();
+                let tile = map.get_tile_mut(Point::new(x, y));
+                tile.terrain = Dirt::default().into();
+                tile.items.clear();
+            }
+        }
+        let body = human_centipede(vec![dead_boy(), dead_boy(), dead_boy()]);
+        let centipede = world.add_unit(Avatar::zombie(dead_boy(), body, Point::new(5, 0)));
+
+        // own tail is not an obstacle
+        let from = world.get_unit(centipede).pos;
+        let path = find_path(
+            &mut WalkerMap::new(&mut world.map(), centipede),
+            from,
+            Point::new(2, 0),
+            100,
+        );
+        assert!(path.complete);
+        assert_eq!(Some(&Point::new(4, 0)), path.steps.first());
+
+        // someone else's tail is
+        let path = find_path(
+            &mut WalkerMap::new(&mut world.map(), 0),
+            Point::new(0, 0),
+            Point::new(4, 0),
+            100,
+        );
+        assert!(!path.complete);
+        let last = *path.steps.last().unwrap();
+        assert!(last.square_distance(Point::new(4, 0)) <= 2);
+        assert!(world.map().get_tile(last).units.is_empty());
+    }
+
+    #[test]
+    pub fn test_player_route() {
+        let mut world = prepare_world();
+        for x in 1..=3 {
+            let mut map = world.map();
+            let tile = map.get_tile_mut(Point::new(x, 0));
+            tile.terrain = Dirt::default().into();
+            tile.items.clear();
+        }
+        world.calc_fov();
+        assert!(world.player_route(Point::new(3, 0), 100).complete);
+
+        // unknown tiles are never walked through or generated
+        let far = Point::new(500, 0);
+        assert!(!world.is_known(far));
+        assert!(!world.player_route(far, 1_000).complete);
+        assert!(!world.map().chunks.contains_key(&far.to_chunk().0));
+
+        // blind player walks by memory
+        let head = BodyPartPath::root(Point::new(0, 0)).child("head");
+        world.player_mut().body.detach(&head.child("left eye"));
+        world.player_mut().body.detach(&head.child("right eye"));
+        world.calc_fov();
+        assert!(!world.is_visible(Point::new(3, 0)));
+        assert!(world.is_known(Point::new(3, 0)));
+        let path = world.player_route(Point::new(3, 0), 100);
+        assert!(path.complete);
+        assert!(path.steps.iter().all(|&p| world.is_known(p)));
+    }
+
+    fn alarm(world: &World, watchman: usize) -> Option<u128> {
+        match &world.get_unit(watchman).soul {
+            Soul::Watchman(_, brain) => brain.alarm,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn test_watchman_alarm() {
+        let mut world = prepare_world();
+        for x in 1..=8 {
+            let mut map = world.map();
+            let tile = map.get_tile_mut(Point::new(x, 0));
+            tile.terrain = Dirt::default().into();
+            tile.items.clear();
+        }
+        let watchman = world.add_unit(Avatar::watchman(dead_boy(), Point::new(5, 0)));
+        // the other watchman can only hear the alarm
+        for x in -10..=20 {
+            world.map().get_tile_mut(Point::new(x, 10)).terrain =
+                Boulder::new(BoulderSize::Huge).into();
+        }
+        let other = world.add_unit(Avatar::watchman(dead_boy(), Point::new(5, 20)));
+        for _ in 0..10 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert_eq!(None, alarm(&world, watchman));
+
+        // the dead are hunted without an alarm
+        add_zombie(&mut world, Point::new(8, 0));
+        world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+        world.tick();
+        assert_eq!(None, alarm(&world, watchman));
+        assert!(!world
+            .log()
+            .new_events()
+            .iter()
+            .any(|e| e.msg.contains("Grave robbers")));
+
+        world.player_mut().wield.clear();
+        world.player_mut().wield.push(Shovel::new().into());
+        let dig = Dig {
+            dir: Direction::West,
+        };
+        world.map().get_tile_mut(Point::new(-1, 0)).terrain = Dirt::default().into();
+        world.player_mut().action = Some(Action::new(0, dig.into(), &world).unwrap());
+        for _ in 0..30 {
+            world.tick();
+        }
+        let until = alarm(&world, watchman).unwrap();
+        assert!(world
+            .log()
+            .new_events()
+            .iter()
+            .any(|e| e.msg.contains("Grave robbers")));
+
+        // alarm is heard, but it is not made longer by hearing
+        for _ in 0..10 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert!(alarm(&world, other).map_or(false, |t| t <= until));
+
+        world.meta.current_tick = until;
+        for _ in 0..100 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+        }
+        assert_eq!(None, alarm(&world, watchman));
+        assert_eq!(None, alarm(&world, other));
+    }
+
+    #[test]
+    pub fn test_stray_dog() {
+        let mut world = prepare_world();
+        let identity = AnimalIdentity::new(3, Sex::Male, BodySize::Normal, FurColor::Black, true);
+        let body = dog_body(&identity, Freshness::Fresh);
+        world.map().get_tile_mut(Point::new(1, 0)).terrain = Boulder::new(BoulderSize::Huge).into();
+        let dog = world.add_unit(Avatar::dog(identity, body, Point::new(2, 0)));
+        assert!(world.get_unit(dog).person().is_none());
+        assert_eq!("The stray dog", world.get_unit(dog).name_for_actions());
+
+        // dog doesn't see through the boulder
+        world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+        world.tick();
+        assert!(!world
+            .log()
+            .new_events()
+            .iter()
+            .any(|e| e.msg.contains("barks")));
+
+        for x in 1..=3 {
+            for y in -1..=1 {
+                world.map().get_tile_mut(Point::new(x, y)).terrain = Dirt::default().into();
+            }
+        }
+        let mut barked = false;
+        for _ in 0..100 {
+            world.player_mut().action = Some(Action::new(0, Skip {}.into(), &world).unwrap());
+            world.tick();
+            barked |= world
+                .log()
+                .new_events()
+                .iter()
+                .any(|e| e.msg == "The stray dog barks at you");
+            if barked {
+                break;
+            }
+        }
+        assert!(barked);
+
+        world.kill_unit(dog);
+        let mut map = world.map();
+        assert!(map
+            .get_tile(Point::new(2, 0))
+            .items
+            .iter()
+            .any(|i| matches!(i, Item::Corpse(..)) && i.name() == "fresh dog corpse"));
+    }
+
+    #[test]
+    pub fn test_action_queue() {
+        let mut world = prepare_world();
+        for x in 1..=2 {
+            let mut map = world.map();
+            let tile = map.get_tile_mut(Point::new(x, 0));
+            tile.terrain = Dirt::default().into();
+            tile.items.clear();
+        }
+        world.map().get_tile_mut(Point::new(3, 0)).terrain = Boulder::new(BoulderSize::Huge).into();
+
+        let walk = Walk {
+            dir: Direction::East,
+        };
+        world.player_mut().action = Some(Action::new(0, walk.into(), &world).unwrap());
+        world
+            .player_mut()
+            .action_queue
+            .extend([walk.into(), walk.into(), walk.into()]);
+        world.tick();
+        assert_eq!(Point::new(2, 0), world.player().pos);
+        assert!(world.player().action.is_none());
+        assert!(world.player().action_queue.is_empty());
+    }
+
+    #[test]
+    pub fn test_multitile_unit() {
+        let mut world = prepare_world();
+        for x in 0..=8 {
+            for y in -1..=1 {
+                world.map().get_tile_mut(Point::new(x, y)).terrain = Dirt::default().into();
+            }
+        }
+        let body = human_centipede(vec![dead_boy(), dead_boy(), dead_boy()]);
+        let centipede = world.add_unit(Avatar::zombie(dead_boy(), body, Point::new(5, 0)));
+        let tiles: Vec<Point> = (3..=5).rev().map(|x| Point::new(x, 0)).collect();
+        assert_eq!(tiles, world.get_unit(centipede).occupied_tiles());
+        for &pos in &tiles {
+            assert!(world.map().get_tile(pos).units.contains(&centipede));
+        }
+
+        assert!(Action::new(
+            centipede,
+            Walk {
+                dir: Direction::East
+            }
+            .into(),
+            &world
+        )
+        .is_ok());
+        world.move_avatar(centipede, Direction::East);
+        assert_eq!(Point::new(6, 0), world.get_unit(centipede).pos);
+        assert!(world.map().get_tile(Point::new(3, 0)).units.is_empty());
+        assert!(world
+            .map()
+            .get_tile(Point::new(4, 0))
+            .units
+            .contains(&centipede));
+
+        // head tile is free but the tail would hit the boulder
+        world.map().get_tile_mut(Point::new(4, -1)).terrain =
+            Boulder::new(BoulderSize::Huge).into();
+        assert!(Action::new(
+            centipede,
+            Walk {
+                dir: Direction::North
+            }
+            .into(),
+            &world
+        )
+        .is_err());
+
+        assert_eq!(Point::new(6, 0), world.get_unit(centipede).head_pos());
+        world
+            .get_unit_mut(centipede)
+            .body
+            .detach(&BodyPartPath::new(
+                Point::new(0, 0),
+                vec!["head".to_string()],
+            ));
+        assert_eq!(Point::new(5, 0), world.get_unit(centipede).head_pos());
+    }
 }
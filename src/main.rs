@@ -6,6 +6,7 @@ mod colors;
 mod fov;
 mod game;
 mod input;
+mod pathfinding;
 mod savefile;
 mod scenes;
 mod settings;
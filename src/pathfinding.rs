@@ -0,0 +1,233 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::f32::consts::SQRT_2;
+
+use geometry::{Direction, Point, DIR8};
+
+pub trait PathMap {
+    /// Ticks to step on the tile or None if it is blocked
+    fn passage_cost(&mut self, pos: Point) -> Option<f32>;
+    /// The cheapest possible step, used for estimating the rest of the path
+    fn min_cost(&self) -> f32;
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Path {
+    /// Tiles to walk through, without the starting one
+    pub steps: Vec<Point>,
+    /// False if the goal is unreachable, blocked or the search ran out of budget,
+    /// then the path leads to the closest found tile
+    pub complete: bool,
+}
+
+impl Path {
+    /// Direction of the first step from the start
+    pub fn first_step(&self, from: Point) -> Option<Direction> {
+        self.steps
+            .first()
+            .map(|p| Direction::from_delta(p.x - from.x, p.y - from.y))
+    }
+}
+
+struct Node {
+    estimate: f32,
+    pos: Point,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    // reversed to make BinaryHeap pop the cheapest node first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    let dx = (a.x - b.x).unsigned_abs() as f32;
+    let dy = (a.y - b.y).unsigned_abs() as f32;
+    dx.max(dy) + (SQRT_2 - 1.0) * dx.min(dy)
+}
+
+/// A* search, `budget` is the maximum count of tiles to look at.
+/// The goal itself may be blocked (e.g. by a unit), then the path leads next to it
+/// and is not complete.
+pub fn find_path<M: PathMap>(map: &mut M, from: Point, to: Point, budget: usize) -> Path {
+    let min_cost = map.min_cost();
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<Point, Point> = HashMap::new();
+    let mut closed: HashSet<Point> = HashSet::new();
+    let mut costs: HashMap<Point, f32> = HashMap::from([(from, 0.0)]);
+    let mut closest = (distance(from, to), from);
+    open.push(Node {
+        estimate: closest.0 * min_cost,
+        pos: from,
+    });
+
+    let mut visited = 0;
+    let mut complete = false;
+    while let Some(Node { pos, .. }) = open.pop() {
+        if pos == to {
+            closest.1 = pos;
+            complete = map.passage_cost(to).is_some();
+            break;
+        }
+        // the same tile could be pushed several times before its cheapest path was found
+        if !closed.insert(pos) {
+            continue;
+        }
+        visited += 1;
+        if visited > budget {
+            break;
+        }
+        let cost = costs[&pos];
+        for dir in DIR8 {
+            let next = pos + dir;
+            if closed.contains(&next) {
+                continue;
+            }
+            let step = if next == to {
+                map.passage_cost(next).unwrap_or(min_cost)
+            } else if let Some(step) = map.passage_cost(next) {
+                step
+            } else {
+                continue;
+            };
+            let k = if next.x != pos.x && next.y != pos.y {
+                SQRT_2
+            } else {
+                1.0
+            };
+            let next_cost = cost + step * k;
+            if costs.get(&next).map_or(true, |&c| next_cost < c) {
+                costs.insert(next, next_cost);
+                came_from.insert(next, pos);
+                let left = distance(next, to);
+                if next != to && left < closest.0 {
+                    closest = (left, next);
+                }
+                open.push(Node {
+                    estimate: next_cost + left * min_cost,
+                    pos: next,
+                });
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let mut current = closest.1;
+    while current != from {
+        // blocked goal is never entered, the path ends next to it
+        if !(current == to && map.passage_cost(current).is_none()) {
+            steps.push(current);
+        }
+        current = came_from[&current];
+    }
+    steps.reverse();
+    Path { steps, complete }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use geometry::{Point, DIR8};
+
+    use super::{find_path, PathMap};
+
+    struct TestMap {
+        walls: HashSet<Point>,
+        mud: HashSet<Point>,
+    }
+
+    impl PathMap for TestMap {
+        fn passage_cost(&mut self, pos: Point) -> Option<f32> {
+            if self.walls.contains(&pos) {
+                None
+            } else if self.mud.contains(&pos) {
+                Some(100.0)
+            } else {
+                Some(10.0)
+            }
+        }
+
+        fn min_cost(&self) -> f32 {
+            10.0
+        }
+    }
+
+    fn wall(x: i32) -> HashSet<Point> {
+        (-3..=3).map(|y| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn test_straight_path() {
+        let mut map = TestMap {
+            walls: HashSet::new(),
+            mud: HashSet::new(),
+        };
+        let path = find_path(&mut map, Point::new(0, 0), Point::new(3, 0), 100);
+        assert!(path.complete);
+        assert_eq!(
+            vec![Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)],
+            path.steps
+        );
+    }
+
+    #[test]
+    fn test_path_around_obstacles() {
+        let mut map = TestMap {
+            walls: wall(2),
+            mud: HashSet::from([Point::new(2, 4)]),
+        };
+        let path = find_path(&mut map, Point::new(0, 0), Point::new(4, 0), 1000);
+        assert!(path.complete);
+        assert_eq!(Some(&Point::new(4, 0)), path.steps.last());
+        assert!(path.steps.iter().all(|p| !map.walls.contains(p)));
+        assert!(path.steps.contains(&Point::new(2, -4)));
+    }
+
+    #[test]
+    fn test_blocked_goal() {
+        let goal = Point::new(3, 0);
+        let mut map = TestMap {
+            walls: HashSet::from([goal]),
+            mud: HashSet::new(),
+        };
+        let path = find_path(&mut map, Point::new(0, 0), goal, 100);
+        assert!(!path.complete);
+        assert_eq!(Some(&Point::new(2, 0)), path.steps.last());
+    }
+
+    #[test]
+    fn test_partial_path() {
+        let mut map = TestMap {
+            walls: HashSet::new(),
+            mud: HashSet::new(),
+        };
+        let path = find_path(&mut map, Point::new(0, 0), Point::new(100, 0), 10);
+        assert!(!path.complete);
+        assert!(!path.steps.is_empty());
+        assert!(path.steps.len() < 100);
+
+        let goal = Point::new(4, 0);
+        let mut map = TestMap {
+            walls: DIR8.iter().map(|&d| goal + d).collect(),
+            mud: HashSet::new(),
+        };
+        let path = find_path(&mut map, Point::new(0, 0), goal, 200);
+        assert!(!path.complete);
+    }
+}
@@ -1,11 +1,19 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::{Path, PathBuf};
 
-use crate::game::{map::chunk::Chunk, Avatar, World};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
 
-use super::{Meta, SAVEFILES_FOLDER};
+use crate::game::{map::chunk::Chunk, Avatar, Log, World};
+
+use super::{
+    backup_path, broken_path,
+    migrations::{self, RawSave, HEADERS_VERSION, REGIONS_VERSION, SAVE_VERSION},
+    region::{log_path, region_path, units_path, RegionPos},
+    GameView, Meta, SAVEFILES_FOLDER,
+};
 
 pub fn savefiles_exists() -> bool {
     let path = Path::new(SAVEFILES_FOLDER);
@@ -23,32 +31,105 @@ pub fn savefiles_exists() -> bool {
         .unwrap_or(false)
 }
 
-pub fn savefiles() -> Vec<Meta> {
+/// Savefile that can't be loaded and the reason why
+#[derive(Debug)]
+pub struct BrokenSave {
+    pub path: PathBuf,
+    pub error: Error,
+}
+
+/// All savefiles, newest go first and broken ones go last
+pub fn savefiles() -> Vec<Result<Meta, BrokenSave>> {
     let path = Path::new(SAVEFILES_FOLDER);
     let mut files = Vec::new();
+    let mut broken = Vec::new();
     if path.exists() {
         for p in path.read_dir().unwrap() {
             let p = p.unwrap().path();
-            if let Some(s) = load(&p) {
-                // TODO: some implementation for invalid (old/broken) savefiles
-                files.push(s);
+            if p.extension().map_or(true, |ext| ext != "save") {
+                continue;
+            }
+            match load(&p) {
+                Ok(meta) => files.push(meta),
+                Err(error) => broken.push(BrokenSave { path: p, error }),
             }
         }
     }
     files.sort();
     files.reverse();
     files
+        .into_iter()
+        .map(Ok)
+        .chain(broken.into_iter().map(Err))
+        .collect()
+}
+
+/// Part of the savefile, every part starts on its own line
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Section {
+    Meta,
+    View,
+    Log,
+    Rng,
+    Units,
+    Chunks,
+    Region(RegionPos),
+}
+
+impl std::fmt::Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Section::Meta => f.write_str("meta"),
+            Section::View => f.write_str("view"),
+            Section::Log => f.write_str("log"),
+            Section::Rng => f.write_str("rng"),
+            Section::Units => f.write_str("units"),
+            Section::Chunks => f.write_str("chunks"),
+            Section::Region(pos) => write!(f, "region {}.{}", pos.x, pos.y),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum Error {
     SystemError(String),
-    DeserializeError(String),
+    MigrationError(String),
+    /// File is truncated before this section
+    UnexpectedEnd(Section),
+    /// File was written later than the savefile and it has no backup that matches it
+    NewerThanSavefile(Section),
+    BrokenLine {
+        section: Section,
+        line: usize,
+        message: String,
+    },
+}
+
+impl Error {
+    fn broken_line(section: Section, line: usize, error: &serde_json::Error) -> Self {
+        Error::BrokenLine {
+            section,
+            line,
+            message: error.to_string(),
+        }
+    }
 }
 
-impl From<serde_json::Error> for Error {
-    fn from(e: serde_json::Error) -> Self {
-        Error::DeserializeError(e.to_string())
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::SystemError(e) => write!(f, "Can't read the file: {}", e),
+            Error::MigrationError(e) => write!(f, "Can't upgrade: {}", e),
+            Error::UnexpectedEnd(section) => write!(f, "File ends before {} section", section),
+            Error::NewerThanSavefile(section) => {
+                write!(f, "The {} is newer than the savefile", section)
+            }
+            Error::BrokenLine {
+                section,
+                line,
+                message,
+            } => write!(f, "Broken {} at line {}: {}", section, line, message),
+        }
     }
 }
 
@@ -58,13 +139,19 @@ impl From<std::io::Error> for Error {
     }
 }
 
-pub fn load(path: &Path) -> Option<Meta> {
-    let file = File::open(path).ok()?;
-    let mut lines = BufReader::new(&file).lines();
-    let meta = lines.next()?.ok()?;
-    serde_json::from_str(meta.as_str())
-        .ok()
-        .map(|s: Meta| s.with_path(path))
+/// Reads only the meta line, checks if the savefile isn't newer than the current version
+pub fn load(path: &Path) -> Result<Meta, Error> {
+    let file = File::open(path)?;
+    let mut reader = Reader::new(&file);
+    let (line, meta) = reader.next(Section::Meta)?;
+    let meta: Meta = parse(meta.as_str(), Section::Meta, line)?;
+    if meta.save_version > SAVE_VERSION {
+        return Err(Error::MigrationError(format!(
+            "Savefile version {} is newer than supported {}",
+            meta.save_version, SAVE_VERSION
+        )));
+    }
+    Ok(meta.with_path(path))
 }
 
 pub fn has_avatar(path: &Path) -> bool {
@@ -75,46 +162,449 @@ pub fn has_avatar(path: &Path) -> bool {
     }
 }
 
+/// Loads the whole world, any broken line is an error.
+/// Chunks are read from regions later, when the map needs them.
 pub fn load_world(path: &Path) -> Result<World, Error> {
-    let file = File::open(path)?;
-    let mut lines = BufReader::new(&file).lines();
-    let meta = lines.next().unwrap()?;
-    let game_view = lines.next().unwrap()?;
-    let log = lines.next().unwrap()?;
-    let mut units_data = Vec::new();
-    loop {
-        let unit = lines.next().unwrap()?;
-        if unit.eq("/units") {
-            break;
+    read_world(path, false).map(|(world, _)| world)
+}
+
+/// Loads everything that is readable: broken chunks are generated again from the seed,
+/// broken units, log and view are dropped.
+/// Meta and the player are necessary. Returns the world and what was lost.
+pub fn salvage_world(path: &Path) -> Result<(World, Vec<Error>), Error> {
+    read_world(path, true)
+}
+
+/// Chunks read from a region file
+pub struct RegionChunks {
+    pub chunks: Vec<Chunk>,
+    /// Broken chunks, they are skipped
+    pub lost: Vec<Error>,
+    /// The file is broken or doesn't match the savefile, the next save should write the region
+    pub outdated: bool,
+}
+
+/// Reads chunks of the region, broken ones are skipped and returned as errors.
+/// Regions are written before the savefile, so a region newer than the savefile
+/// of the save number `saved` is left from an unfinished save or the savefile was restored
+/// from a backup, then the newest backup of the region that is not newer is read.
+/// A copy of a broken file is kept aside because the next save rewrites the region.
+/// There is no region file until some of its chunks are changed.
+pub fn load_region(regions_dir: &Path, region: RegionPos, saved: u64) -> RegionChunks {
+    let section = Section::Region(region);
+    let mut result = RegionChunks {
+        chunks: Vec::new(),
+        lost: Vec::new(),
+        outdated: false,
+    };
+    let path = region_path(regions_dir, region);
+    let versions = file_versions(&path);
+    let matching = versions
+        .iter()
+        .find(|p| file_save(p, section).unwrap_or(0) <= saved);
+    let version = match (matching, versions.last()) {
+        (Some(version), _) => version,
+        (None, Some(oldest)) => {
+            result.lost.push(Error::NewerThanSavefile(section));
+            oldest
+        }
+        (None, None) => return result,
+    };
+    result.outdated = *version != path;
+    let file = match File::open(version) {
+        Ok(file) => file,
+        Err(e) => {
+            result.lost.push(e.into());
+            return result;
+        }
+    };
+    let mut reader = Reader::new(&file);
+    let mut salvager = Salvager {
+        salvage: true,
+        lost: Vec::new(),
+    };
+    result.chunks = match read_region(&mut reader, &mut salvager, section) {
+        Ok(chunks) => chunks,
+        Err(error) => {
+            salvager.lost.push(error);
+            Vec::new()
+        }
+    };
+    if !salvager.lost.is_empty() {
+        result.outdated = true;
+        if let Err(error) = std::fs::copy(version, broken_path(version)) {
+            salvager.lost.push(error.into());
+        }
+    }
+    result.lost.append(&mut salvager.lost);
+    result
+}
+
+/// The file and its backups from the newest to the oldest
+fn file_versions(path: &Path) -> Vec<PathBuf> {
+    std::iter::once(path.to_path_buf())
+        .chain((1..).map(|i| backup_path(path, i)))
+        .take_while(|p| p.is_file())
+        .collect()
+}
+
+/// Number of the save from the header of the file, None if the header is broken.
+/// Files written before saves were numbered are 0.
+fn file_save(path: &Path, section: Section) -> Option<u64> {
+    let file = File::open(path).ok()?;
+    let (_, header) = read_value(&mut Reader::new(&file), section).ok()?;
+    Some(header.get("save").and_then(Value::as_u64).unwrap_or(0))
+}
+
+/// Savefile lines with their numbers for error messages
+struct Reader<'a> {
+    lines: Lines<BufReader<&'a File>>,
+    line: usize,
+    /// Line that was read too early and should be read again
+    back: Option<(usize, String)>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(file: &'a File) -> Self {
+        Self {
+            lines: BufReader::new(file).lines(),
+            line: 0,
+            back: None,
+        }
+    }
+
+    fn put_back(&mut self, line: usize, data: String) {
+        self.back = Some((line, data));
+    }
+
+    fn next(&mut self, section: Section) -> Result<(usize, String), Error> {
+        self.next_optional()?.ok_or(Error::UnexpectedEnd(section))
+    }
+
+    fn next_optional(&mut self) -> Result<Option<(usize, String)>, Error> {
+        if let Some(back) = self.back.take() {
+            return Ok(Some(back));
+        }
+        self.line += 1;
+        let line = self.line;
+        Ok(self.lines.next().transpose()?.map(|data| (line, data)))
+    }
+}
+
+/// Collects errors instead of failing when salvaging
+struct Salvager {
+    salvage: bool,
+    lost: Vec<Error>,
+}
+
+impl Salvager {
+    fn recover<T>(&mut self, result: Result<T, Error>) -> Result<Option<T>, Error> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if self.salvage => {
+                // truncated file is reported only once
+                let repeated = matches!(error, Error::UnexpectedEnd(..))
+                    && self
+                        .lost
+                        .iter()
+                        .any(|e| matches!(e, Error::UnexpectedEnd(..)));
+                if !repeated {
+                    self.lost.push(error);
+                }
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn parse<T: DeserializeOwned>(data: &str, section: Section, line: usize) -> Result<T, Error> {
+    serde_json::from_str(data).map_err(|e| Error::broken_line(section, line, &e))
+}
+
+fn convert<T: DeserializeOwned>(value: Value, section: Section, line: usize) -> Result<T, Error> {
+    serde_json::from_value(value).map_err(|e| Error::broken_line(section, line, &e))
+}
+
+fn read_value(reader: &mut Reader, section: Section) -> Result<(usize, Value), Error> {
+    let (line, data) = reader.next(section)?;
+    parse(data.as_str(), section, line).map(|value| (line, value))
+}
+
+fn read_region(
+    reader: &mut Reader,
+    salvager: &mut Salvager,
+    section: Section,
+) -> Result<Vec<Chunk>, Error> {
+    // region without header is considered as the oldest one
+    let header = salvager
+        .recover(read_value(reader, section))?
+        .map_or_else(|| json!({ "save_version": REGIONS_VERSION }), |(_, v)| v);
+    let chunks = read_list(reader, salvager, section, None)?;
+    let (lines, chunks): (Vec<usize>, Vec<Value>) = chunks.into_iter().unzip();
+    let mut save = RawSave {
+        meta: header,
+        game_view: Value::Null,
+        log: Value::Null,
+        units: Vec::new(),
+        chunks,
+    };
+    migrations::migrate(&mut save)?;
+    let mut chunks = Vec::with_capacity(save.chunks.len());
+    for (chunk, line) in save.chunks.into_iter().zip(lines) {
+        if let Some(chunk) = salvager.recover(convert::<Chunk>(chunk, section, line))? {
+            chunks.push(chunk);
         }
-        units_data.push(unit);
     }
-    let mut chunks_data = Vec::new();
+    Ok(chunks)
+}
+
+/// Reads lines of a section until its closing line or until the end of its own file
+fn read_list(
+    reader: &mut Reader,
+    salvager: &mut Salvager,
+    section: Section,
+    end: Option<&str>,
+) -> Result<Vec<(usize, Value)>, Error> {
+    let mut values = Vec::new();
     loop {
-        let chunk = lines.next().unwrap()?;
-        if chunk.eq("/chunks") {
+        let next = if end.is_some() {
+            salvager.recover(reader.next(section))?
+        } else {
+            reader.next_optional()?
+        };
+        let (line, data) = match next {
+            Some(next) => next,
+            None => break,
+        };
+        if end == Some(data.as_str()) {
             break;
         }
-        chunks_data.push(chunk);
+        // the first unit is the player, it can't be lost
+        let value = if section == Section::Units && values.is_empty() {
+            Some(parse(data.as_str(), section, line)?)
+        } else {
+            salvager.recover(parse(data.as_str(), section, line))?
+        };
+        if let Some(value) = value {
+            values.push((line, value));
+        }
     }
+    Ok(values)
+}
+
+fn open(path: &Path) -> Result<File, Error> {
+    File::open(path).map_err(|e| Error::SystemError(format!("{}: {}", path.display(), e)))
+}
+
+/// The only value of the file, the header goes before it if there is one
+fn read_file(path: &Path, section: Section, header: bool) -> Result<(usize, Value), Error> {
+    let file = open(path)?;
+    let mut reader = Reader::new(&file);
+    if header {
+        reader.next(section)?;
+    }
+    read_value(&mut reader, section)
+}
 
-    let mut units = Vec::with_capacity(units_data.len());
-    for unit in &units_data {
-        let unit: Avatar = serde_json::from_str(unit).unwrap();
-        units.push(unit);
+/// Files with parts of the world written by one save
+struct SaveSet {
+    /// Savefile or its backup
+    main: PathBuf,
+    units: PathBuf,
+    log: PathBuf,
+}
+
+/// The newest savefile with units and log written by the same save.
+/// The savefile is written last, so units and log newer than it are left from an unfinished save
+/// and their backups are read, the savefile backups are tried if there are no matching ones.
+/// Salvaged world takes the newest files when nothing matches.
+fn save_set(path: &Path, salvager: &mut Salvager) -> Result<SaveSet, Error> {
+    let units = units_path(path);
+    let log = log_path(path);
+    for main in file_versions(path) {
+        let meta = match read_file(&main, Section::Meta, false) {
+            Ok((_, meta)) => meta,
+            Err(..) => continue,
+        };
+        if migrations::save_version(&meta) < HEADERS_VERSION {
+            return Ok(SaveSet { main, units, log });
+        }
+        let saved = meta.get("saves").and_then(Value::as_u64).unwrap_or(0);
+        let matching = |path: &Path, section: Section| {
+            file_versions(path)
+                .into_iter()
+                .find(|p| file_save(p, section) == Some(saved))
+        };
+        if let (Some(units), Some(log)) = (
+            matching(&units, Section::Units),
+            matching(&log, Section::Log),
+        ) {
+            return Ok(SaveSet { main, units, log });
+        }
     }
+    salvager.recover::<()>(Err(Error::NewerThanSavefile(Section::Units)))?;
+    Ok(SaveSet {
+        main: path.to_path_buf(),
+        units,
+        log,
+    })
+}
+
+/// Line numbers of savefile sections for error messages
+struct SectionLines {
+    meta: usize,
+    view: usize,
+    log: usize,
+    units: Vec<usize>,
+    chunks: Vec<usize>,
+}
+
+/// Reads all sections of the savefile without migrating them,
+/// random generator state is returned as it is
+fn read_raw(
+    path: &Path,
+    salvager: &mut Salvager,
+) -> Result<(RawSave, SectionLines, Option<(usize, String)>), Error> {
+    let set = save_set(path, salvager)?;
+    let file = open(&set.main)?;
+    let mut reader = Reader::new(&file);
 
-    let mut chunks = HashMap::with_capacity(chunks_data.len());
-    for chunk in &chunks_data {
-        let chunk: Chunk = serde_json::from_str(chunk).unwrap();
-        chunks.insert(chunk.pos, chunk);
+    let (meta_line, meta) = reader.next(Section::Meta)?;
+    let meta: Value = parse(meta.as_str(), Section::Meta, meta_line)?;
+    let version = migrations::save_version(&meta);
+    // older saves have everything in one file
+    let single_file = version < REGIONS_VERSION;
+    let game_view = salvager.recover(read_value(&mut reader, Section::View))?;
+    let log = if single_file {
+        salvager.recover(read_value(&mut reader, Section::Log))?
+    } else {
+        None
+    };
+    let rng = match salvager.recover(reader.next(Section::Rng))? {
+        // the oldest saves have no random generator state, the player goes right after the log
+        Some((line, data)) if version == 0 && is_unit(data.as_str()) => {
+            reader.put_back(line, data);
+            None
+        }
+        rng => rng,
+    };
+    let (units, chunks, log) = if single_file {
+        let units = read_list(&mut reader, salvager, Section::Units, Some("/units"))?;
+        let chunks = read_list(&mut reader, salvager, Section::Chunks, Some("/chunks"))?;
+        (units, chunks, log)
+    } else {
+        let headers = version >= HEADERS_VERSION;
+        let units_file = open(&set.units)?;
+        let mut units_reader = Reader::new(&units_file);
+        if headers {
+            salvager.recover(units_reader.next(Section::Units))?;
+        }
+        let units = read_list(&mut units_reader, salvager, Section::Units, None)?;
+        let log = salvager.recover(read_file(&set.log, Section::Log, headers))?;
+        (units, Vec::new(), log)
+    };
+    if units.is_empty() {
+        return Err(Error::UnexpectedEnd(Section::Units));
     }
 
-    Ok(World::new(
-        serde_json::from_str(meta.as_str()).map(|s: Meta| s.with_path(path))?,
-        serde_json::from_str(game_view.as_str())?,
-        serde_json::from_str(log.as_str())?,
+    let (unit_lines, units): (Vec<usize>, Vec<Value>) = units.into_iter().unzip();
+    let (chunk_lines, chunks): (Vec<usize>, Vec<Value>) = chunks.into_iter().unzip();
+    let (view_line, game_view) = game_view.map_or((0, None), |(l, v)| (l, Some(v)));
+    let (log_line, log) = log.map_or((0, None), |(l, v)| (l, Some(v)));
+    let save = RawSave {
+        meta,
+        game_view: game_view.unwrap_or(Value::Null),
+        log: log.unwrap_or(Value::Null),
         units,
         chunks,
-    ))
+    };
+    let lines = SectionLines {
+        meta: meta_line,
+        view: view_line,
+        log: log_line,
+        units: unit_lines,
+        chunks: chunk_lines,
+    };
+    Ok((save, lines, rng))
+}
+
+fn is_unit(data: &str) -> bool {
+    serde_json::from_str::<Value>(data).map_or(false, |value| value.get("body").is_some())
+}
+
+/// Older savefile is upgraded without loading the world to be sure all its units survive it,
+/// other broken lines are found and salvaged on loading.
+/// The whole savefile is read, so it's done when the save is opened, not when it's listed.
+pub fn check_units(meta: &Meta) -> Result<(), Error> {
+    if meta.save_version >= SAVE_VERSION {
+        return Ok(());
+    }
+    let mut salvager = Salvager {
+        salvage: true,
+        lost: Vec::new(),
+    };
+    let (mut save, lines, _) = read_raw(&meta.path, &mut salvager)?;
+    migrations::migrate(&mut save)?;
+    for (unit, line) in save.units.into_iter().zip(lines.units) {
+        convert::<Avatar>(unit, Section::Units, line)?;
+    }
+    Ok(())
+}
+
+fn read_world(path: &Path, salvage: bool) -> Result<(World, Vec<Error>), Error> {
+    let mut salvager = Salvager {
+        salvage,
+        lost: Vec::new(),
+    };
+    let (mut save, lines, rng) = read_raw(path, &mut salvager)?;
+    migrations::migrate(&mut save)?;
+
+    let meta: Meta = convert(save.meta, Section::Meta, lines.meta)?;
+    let game_view = if save.game_view.is_null() {
+        None
+    } else {
+        salvager.recover(convert::<GameView>(
+            save.game_view,
+            Section::View,
+            lines.view,
+        ))?
+    };
+    let log = if save.log.is_null() {
+        None
+    } else {
+        salvager.recover(convert::<Log>(save.log, Section::Log, lines.log))?
+    };
+
+    let mut avatars = Vec::with_capacity(save.units.len());
+    for (unit, line) in save.units.into_iter().zip(lines.units) {
+        let unit = convert::<Avatar>(unit, Section::Units, line);
+        if avatars.is_empty() {
+            avatars.push(unit?);
+        } else if let Some(unit) = salvager.recover(unit)? {
+            avatars.push(unit);
+        }
+    }
+
+    let mut chunks = HashMap::with_capacity(save.chunks.len());
+    for (chunk, line) in save.chunks.into_iter().zip(lines.chunks) {
+        if let Some(chunk) = salvager.recover(convert::<Chunk>(chunk, Section::Chunks, line))? {
+            chunks.insert(chunk.pos, chunk);
+        }
+    }
+
+    let mut world = World::new(
+        meta.with_path(path),
+        game_view.unwrap_or_default(),
+        log.unwrap_or_else(Log::new),
+        avatars,
+        chunks,
+    );
+    if let Some((line, rng)) = rng {
+        if let Some(rng) = salvager.recover(parse(rng.as_str(), Section::Rng, line))? {
+            world = world.with_rng(rng);
+        }
+    }
+
+    Ok((world, salvager.lost))
 }
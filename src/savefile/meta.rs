@@ -4,6 +4,8 @@ use std::time::SystemTime;
 
 use crate::VERSION;
 
+use super::SAVE_VERSION;
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Meta {
     #[serde(skip)]
@@ -11,8 +13,14 @@ pub struct Meta {
     pub name: String,
     pub seed: String,
     pub version: String,
+    /// Layout of the savefile data, older saves are migrated on loading
+    #[serde(default)]
+    pub save_version: u32,
     pub time: SystemTime,
     pub current_tick: u128,
+    /// Number of the last save, every file written by a save is marked with it
+    #[serde(default)]
+    pub saves: u64,
 }
 
 impl Meta {
@@ -25,8 +33,10 @@ impl Meta {
             name: name.into(),
             seed: seed.into(),
             version: VERSION.to_string(),
+            save_version: SAVE_VERSION,
             time: SystemTime::now(),
             current_tick: 0,
+            saves: 0,
         }
     }
 
@@ -38,6 +48,8 @@ impl Meta {
     pub fn update_before_save(&mut self) {
         self.time = SystemTime::now();
         self.version = VERSION.to_string();
+        self.save_version = SAVE_VERSION;
+        self.saves += 1;
     }
 }
 
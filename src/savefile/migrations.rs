@@ -0,0 +1,322 @@
+use serde_json::{json, Value};
+
+use super::load::Error;
+
+/// Layout of the savefile data, increase it with every change that breaks old saves
+/// and add a migration step for it
+pub const SAVE_VERSION: u32 = 5;
+
+/// Since this version chunks are stored in region files, units and log in their own files
+pub const REGIONS_VERSION: u32 = 2;
+
+/// Since this version units and log files start with a header like region files
+pub const HEADERS_VERSION: u32 = 4;
+
+/// Savefile split into sections but not deserialized yet,
+/// random generator state is not here because its numbers don't fit in `Value`
+#[derive(Debug)]
+pub struct RawSave {
+    pub meta: Value,
+    pub game_view: Value,
+    pub log: Value,
+    pub units: Vec<Value>,
+    pub chunks: Vec<Value>,
+}
+
+type Migration = fn(&mut RawSave);
+
+/// Step `i` upgrades savefile from version `i` to `i + 1`
+const MIGRATIONS: [Migration; SAVE_VERSION as usize] = [
+    animal_identities,
+    region_files,
+    unit_fields,
+    file_headers,
+    remembered_names,
+];
+
+/// Version of the savefile layout, saves made before versioning are 0
+pub fn save_version(meta: &Value) -> u32 {
+    meta.get("save_version")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32)
+}
+
+/// Upgrades older savefile to the current layout step by step
+pub fn migrate(save: &mut RawSave) -> Result<(), Error> {
+    let version = save_version(&save.meta);
+    if version > SAVE_VERSION {
+        return Err(Error::MigrationError(format!(
+            "Savefile version {} is newer than supported {}",
+            version, SAVE_VERSION
+        )));
+    }
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(save);
+        if let Some(meta) = save.meta.as_object_mut() {
+            meta.insert("save_version".to_string(), json!(i + 1));
+        }
+    }
+    Ok(())
+}
+
+/// Dog brains got `AnimalIdentity`, saves made before versioning had no living dogs
+fn animal_identities(save: &mut RawSave) {
+    for unit in &mut save.units {
+        add_brain_identities(unit, &Value::Null, &Value::Null);
+    }
+    for chunk in &mut save.chunks {
+        add_brain_identities(chunk, &Value::Null, &Value::Null);
+    }
+}
+
+/// Units are not saved with chunks anymore: chunks are loaded lazily,
+/// so units are placed on tiles from the list of units
+fn region_files(save: &mut RawSave) {
+    for chunk in &mut save.chunks {
+        if let Some(tiles) = chunk.get_mut("tiles").and_then(Value::as_array_mut) {
+            for tile in tiles {
+                if let Some(tile) = tile.as_object_mut() {
+                    tile.remove("u");
+                }
+            }
+        }
+    }
+}
+
+/// Units got action queue and death flag, cloaks got pockets
+fn unit_fields(save: &mut RawSave) {
+    for unit in &mut save.units {
+        if let Some(unit) = unit.as_object_mut() {
+            unit.entry("action_queue").or_insert_with(|| json!([]));
+            unit.entry("dead").or_insert(Value::Bool(false));
+        }
+        add_pockets(unit);
+    }
+    for chunk in &mut save.chunks {
+        add_pockets(chunk);
+    }
+}
+
+/// Units and log files got headers, the reader skips them and the data stays the same
+fn file_headers(_save: &mut RawSave) {}
+
+/// Remembered tiles kept only sprites of items, now they keep names and the top sprite.
+/// Names of old memories are made from sprites, it's the best guess.
+fn remembered_names(save: &mut RawSave) {
+    for chunk in &mut save.chunks {
+        if let Some(tiles) = chunk.get_mut("tiles").and_then(Value::as_array_mut) {
+            for memory in tiles.iter_mut().filter_map(|t| t.get_mut("m")) {
+                if let Some(memory) = memory.as_object_mut() {
+                    let sprites = memory.remove("i").unwrap_or_else(|| json!([]));
+                    let sprites = sprites.as_array().cloned().unwrap_or_default();
+                    let names: Vec<Value> = sprites
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(|s| json!(s.replace('_', " ")))
+                        .collect();
+                    memory.insert("n".to_string(), Value::Array(names));
+                    memory.insert(
+                        "s".to_string(),
+                        sprites.last().cloned().unwrap_or(Value::Null),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Cloaks are everywhere: worn, wielded, on the ground and on corpses
+fn add_pockets(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Object(cloak)) = object.get_mut("cloak") {
+                cloak
+                    .entry("p")
+                    .or_insert_with(|| json!({"i": [], "v": 2_000, "m": 3_000}));
+            }
+            for child in object.values_mut() {
+                add_pockets(child);
+            }
+        }
+        Value::Array(array) => {
+            for child in array {
+                add_pockets(child);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Brains inherit fur and sex from the closest dog head or torso they are attached to
+fn add_brain_identities(value: &mut Value, fur: &Value, sex: &Value) {
+    match value {
+        Value::Object(object) => {
+            let parent = object
+                .get("t")
+                .and_then(|t| t.get("dog_head").or_else(|| t.get("dog_torso")))
+                .and_then(Value::as_array)
+                .cloned();
+            let (fur, sex) = parent.map_or_else(
+                || (fur.clone(), sex.clone()),
+                |p| (p[1].clone(), p[2].clone()),
+            );
+            if let Some(brain) = object.get_mut("t").and_then(|t| t.get_mut("dog_brain")) {
+                if brain.is_object() {
+                    let identity = animal_identity(brain, &fur, &sex);
+                    *brain = json!([brain.take(), identity]);
+                }
+            }
+            for child in object.values_mut() {
+                add_brain_identities(child, &fur, &sex);
+            }
+        }
+        Value::Array(array) => {
+            for child in array {
+                add_brain_identities(child, fur, sex);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn animal_identity(organ_data: &Value, fur: &Value, sex: &Value) -> Value {
+    json!({
+        "n": null,
+        "a": organ_data.get("a").cloned().unwrap_or_else(|| json!(1)),
+        "s": if sex.is_null() { json!("f") } else { sex.clone() },
+        "z": organ_data.get("s").cloned().unwrap_or_else(|| json!("3")),
+        "f": if fur.is_null() { json!("2") } else { fur.clone() },
+        "l": organ_data.get("l").cloned().unwrap_or(Value::Bool(false)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{migrate, save_version, RawSave, SAVE_VERSION};
+
+    fn organ_data() -> Value {
+        json!({"f": "f", "a": 3, "s": "3", "l": true})
+    }
+
+    #[test]
+    fn test_brain_identities() {
+        let brain = json!({"n": "brain", "t": {"dog_brain": organ_data()}, "o": [], "i": []});
+        let head = json!({"n": "head", "t": {"dog_head": [organ_data(), "8", "m"]}, "o": [], "i": [brain.clone()]});
+        let torso = json!({"n": "torso", "t": {"dog_torso": [organ_data(), "8", "m"]}, "o": [head], "i": []});
+        let mut save = RawSave {
+            meta: json!({"name": "test"}),
+            game_view: json!({}),
+            log: json!({}),
+            units: vec![json!({
+                "body": {"p": [[{"x": 0, "y": 0}, torso]], "w": []},
+            })],
+            chunks: vec![json!({"tiles": [{"items": [{"body_part": brain}]}]})],
+        };
+        assert_eq!(0, save_version(&save.meta));
+        migrate(&mut save).unwrap();
+        assert_eq!(SAVE_VERSION, save_version(&save.meta));
+
+        let unit = &save.units[0];
+        let brain = unit.pointer("/body/p/0/1/o/0/i/0/t/dog_brain").unwrap();
+        assert_eq!(Some(&json!(3)), brain.pointer("/0/a"));
+        assert_eq!(Some(&json!("8")), brain.pointer("/1/f"));
+
+        // loose brain doesn't know its fur
+        let brain = save.chunks[0]
+            .pointer("/tiles/0/items/0/body_part/t/dog_brain")
+            .unwrap();
+        assert_eq!(Some(&json!("2")), brain.pointer("/1/f"));
+
+        // nothing to do for the current version
+        let units = save.units.clone();
+        migrate(&mut save).unwrap();
+        assert_eq!(units, save.units);
+    }
+
+    #[test]
+    fn test_unit_fields() {
+        let cloak = json!({"cloak": {}});
+        let mut save = RawSave {
+            meta: json!({"save_version": 1}),
+            game_view: json!({}),
+            log: json!({}),
+            units: vec![json!({
+                "body": {"p": [], "w": [cloak.clone()]},
+                "wield": [{"corpse": {"character": {}, "body": {"p": [], "w": [cloak.clone()]}}}],
+                "soul": {"Player": {}},
+            })],
+            chunks: vec![json!({"tiles": [{"i": [cloak], "u": [0]}]})],
+        };
+        migrate(&mut save).unwrap();
+        assert_eq!(SAVE_VERSION, save_version(&save.meta));
+
+        let unit = &save.units[0];
+        assert_eq!(Some(&json!([])), unit.get("action_queue"));
+        assert_eq!(Some(&json!(false)), unit.get("dead"));
+        let pockets = json!({"i": [], "v": 2_000, "m": 3_000});
+        assert_eq!(Some(&pockets), unit.pointer("/body/w/0/cloak/p"));
+        assert_eq!(
+            Some(&pockets),
+            unit.pointer("/wield/0/corpse/body/w/0/cloak/p")
+        );
+        let tile = &save.chunks[0]["tiles"][0];
+        assert_eq!(Some(&pockets), tile.pointer("/i/0/cloak/p"));
+        assert!(tile.get("u").is_none());
+
+        // dead unit stays dead and full pockets are not emptied
+        let mut save = RawSave {
+            meta: json!({"save_version": 2}),
+            game_view: json!({}),
+            log: json!({}),
+            units: vec![json!({
+                "body": {"p": [], "w": [{"cloak": {"p": {"i": [{"shovel": {}}], "v": 1, "m": 1}}}]},
+                "dead": true,
+            })],
+            chunks: Vec::new(),
+        };
+        migrate(&mut save).unwrap();
+        let unit = &save.units[0];
+        assert_eq!(Some(&json!(true)), unit.get("dead"));
+        assert_eq!(
+            Some(&json!([{"shovel": {}}])),
+            unit.pointer("/body/w/0/cloak/p/i")
+        );
+    }
+
+    #[test]
+    fn test_remembered_names() {
+        let mut save = RawSave {
+            meta: json!({"save_version": 4}),
+            game_view: json!({}),
+            log: json!({}),
+            units: Vec::new(),
+            chunks: vec![json!({"tiles": [
+                {"t": {}, "i": [], "m": {"t": {}, "i": ["shovel", "grave_stone"]}},
+                {"t": {}, "i": [], "m": {"t": {}, "i": []}},
+                {"t": {}, "i": []},
+            ]})],
+        };
+        migrate(&mut save).unwrap();
+        let tiles = &save.chunks[0]["tiles"];
+        assert_eq!(json!(["shovel", "grave stone"]), tiles[0]["m"]["n"]);
+        assert_eq!(json!("grave_stone"), tiles[0]["m"]["s"]);
+        assert!(tiles[0]["m"].get("i").is_none());
+        assert_eq!(json!([]), tiles[1]["m"]["n"]);
+        assert_eq!(Value::Null, tiles[1]["m"]["s"]);
+        assert!(tiles[2].get("m").is_none());
+    }
+
+    #[test]
+    fn test_newer_version() {
+        let mut save = RawSave {
+            meta: json!({"save_version": SAVE_VERSION + 1}),
+            game_view: json!({}),
+            log: json!({}),
+            units: Vec::new(),
+            chunks: Vec::new(),
+        };
+        assert!(migrate(&mut save).is_err());
+    }
+}
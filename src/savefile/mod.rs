@@ -1,52 +1,399 @@
 use std::path::Path;
 
 pub use game_view::GameView;
-pub use load::{has_avatar, load, load_world, savefiles, savefiles_exists};
+pub use load::{
+    check_units, has_avatar, load, load_region, load_world, salvage_world, savefiles,
+    savefiles_exists, BrokenSave, Error as LoadError, RegionChunks,
+};
 pub use meta::Meta;
-pub use save::{create, save, Error as SaveError};
+pub use migrations::SAVE_VERSION;
+pub use region::{regions_dir, unsaved_dir, RegionPos};
+pub use save::{
+    clear_unloaded, create, header, region_data, save, save_unloaded, Error as SaveError, WorldData,
+};
+
+use region::world_dir;
+use save::{backup_path, broken_path, tmp_path};
 
 mod game_view;
 mod load;
 mod meta;
+mod migrations;
+mod region;
 mod save;
 
 const SAVEFILES_FOLDER: &str = "save";
 
+/// Deletes the savefile with all its backups and the world directory
 pub fn delete(path: &Path) {
     if path.exists() {
         std::fs::remove_file(path).ok();
     }
+    std::fs::remove_dir_all(world_dir(path)).ok();
+    std::fs::remove_file(tmp_path(path)).ok();
+    let mut i = 1;
+    while backup_path(path, i).is_file() {
+        std::fs::remove_file(backup_path(path, i)).ok();
+        i += 1;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
-    use crate::game::world::tests::prepare_world;
+    use geometry::Point;
+    use rand::Rng;
+
+    use crate::game::{
+        map::{item::ItemInteract, items::Shovel, Chunk},
+        world::tests::{add_zombie, prepare_world},
+        ChunkPos, Log,
+    };
 
-    use super::{delete, load, load_world};
+    use super::{
+        backup_path, broken_path, check_units, delete, load,
+        load::{Error, Section},
+        load_world,
+        region::{log_path, region_path, units_path},
+        regions_dir, salvage_world, tmp_path, RegionPos, SAVE_VERSION,
+    };
 
     #[test]
     fn test_save_and_load() {
         let path = PathBuf::from("save/test.save");
         let mut world = prepare_world();
         world.meta.path = path.clone();
-        world.save();
+        world.save(0).unwrap();
 
         let meta = load(&path).unwrap();
         assert_eq!(meta.name, world.meta.name);
         assert_eq!(meta.current_tick, world.meta.current_tick);
         assert_eq!(meta.version, world.meta.version);
         assert_eq!(meta.seed, world.meta.seed);
+        assert_eq!(meta.save_version, SAVE_VERSION);
 
         let world2 = load_world(&path).unwrap();
         assert_eq!(world.game_view.zoom, world2.game_view.zoom);
         assert_eq!(world.player().pos, world2.player().pos);
         assert_eq!(
-            world.player().person().mind.name,
-            world2.player().person().mind.name
+            world.player().person().unwrap().mind.name,
+            world2.player().person().unwrap().mind.name
+        );
+        assert_eq!(world.rng().gen::<u64>(), world2.rng().gen::<u64>());
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_broken_save() {
+        let path = PathBuf::from("save/broken.save");
+        let mut world = prepare_world();
+        world.meta.path = path.clone();
+        add_zombie(&mut world, Point::new(1, 0));
+        world.save(0).unwrap();
+
+        // header, player and zombie
+        let units_file = units_path(&path);
+        let units = std::fs::read_to_string(&units_file).unwrap();
+        let mut units: Vec<&str> = units.lines().collect();
+        assert_eq!(3, units.len());
+        units[2] = "{\"broken\": true}";
+        std::fs::write(&units_file, units.join("\n")).unwrap();
+        // header and the chunk with units
+        let region_file = region_path(&regions_dir(&path), RegionPos::new(0, 0));
+        let region = std::fs::read_to_string(&region_file).unwrap();
+        let mut region: Vec<&str> = region.lines().collect();
+        assert_eq!(2, region.len());
+        region[1] = "{\"broken";
+        std::fs::write(&region_file, region.join("\n")).unwrap();
+
+        let error = load_world(&path).err().unwrap();
+        assert!(matches!(
+            error,
+            Error::BrokenLine {
+                section: Section::Units,
+                line: 3,
+                ..
+            }
+        ));
+        let (world2, lost) = salvage_world(&path).unwrap();
+        assert_eq!(1, lost.len());
+        assert!(matches!(
+            world2.map().lost.as_slice(),
+            [Error::BrokenLine {
+                section: Section::Region(RegionPos { x: 0, y: 0 }),
+                line: 2,
+                ..
+            }]
+        ));
+        // the broken region is kept aside until the player deals with it
+        assert!(broken_path(&region_file).is_file());
+        assert_eq!(world.player().pos, world2.player().pos);
+        assert!(world2.map().get_tile(Point::new(0, 0)).units.contains(&0));
+        assert!(world2.map().get_tile(Point::new(1, 0)).units.is_empty());
+
+        let main = std::fs::read_to_string(&path).unwrap();
+        let main: Vec<&str> = main.lines().collect();
+        std::fs::write(&path, main[..2].join("\n")).unwrap();
+        let error = load_world(&path).err().unwrap();
+        assert!(matches!(error, Error::UnexpectedEnd(Section::Rng)));
+        let (_, lost) = salvage_world(&path).unwrap();
+        assert_eq!(2, lost.len());
+        assert!(matches!(lost[0], Error::UnexpectedEnd(Section::Rng)));
+        std::fs::remove_file(&units_file).unwrap();
+        let error = salvage_world(&path).err().unwrap();
+        assert!(matches!(error, Error::SystemError(..)));
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_broken_unit() {
+        let path = PathBuf::from("save/broken_unit.save");
+        let mut world = prepare_world();
+        world.meta.path = path.clone();
+        add_zombie(&mut world, Point::new(1, 0));
+        // in another region, it's not read until needed
+        let far = Point::new(300, 0);
+        let far_zombie = add_zombie(&mut world, far);
+        world.save(0).unwrap();
+
+        let units_file = units_path(&path);
+        let units = std::fs::read_to_string(&units_file).unwrap();
+        let mut units: Vec<&str> = units.lines().collect();
+        assert_eq!(4, units.len());
+        units[2] = "{\"broken\": true}";
+        std::fs::write(&units_file, units.join("\n")).unwrap();
+
+        let (world2, lost) = salvage_world(&path).unwrap();
+        assert_eq!(1, lost.len());
+        assert!(world2.map().get_tile(Point::new(1, 0)).units.is_empty());
+        // ids after the lost unit are shifted
+        let moved = far_zombie - 1;
+        assert_eq!(far, world2.get_unit(moved).pos);
+        let units = world2.map().get_tile(far).units.clone();
+        assert_eq!(1, units.len());
+        assert!(units.contains(&moved));
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_single_file_save() {
+        let path = PathBuf::from("save/single.save");
+        let mut world = prepare_world();
+        world.meta.path = path.clone();
+        let mut meta = world.meta.clone();
+        meta.save_version = 1;
+        let lines = [
+            serde_json::to_string(&meta).unwrap(),
+            serde_json::to_string(&world.game_view).unwrap(),
+            serde_json::to_string(&Log::new()).unwrap(),
+            serde_json::to_string(&*world.rng()).unwrap(),
+            serde_json::to_string(world.player()).unwrap(),
+            "/units".to_string(),
+            serde_json::to_string(world.map().get_chunk(ChunkPos::new(0, 0))).unwrap(),
+            "/chunks".to_string(),
+        ];
+        std::fs::create_dir_all("save").unwrap();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let mut world2 = load_world(&path).unwrap();
+        assert_eq!(world.player().pos, world2.player().pos);
+        assert!(world2.map().changed.contains(&ChunkPos::new(0, 0)));
+        world2.save(0).unwrap();
+        assert!(world2.map().changed.is_empty());
+        assert!(units_path(&path).is_file());
+        assert!(region_path(&regions_dir(&path), RegionPos::new(0, 0)).is_file());
+
+        let world3 = load_world(&path).unwrap();
+        assert_eq!(SAVE_VERSION, world3.meta.save_version);
+        assert_eq!(world.player().pos, world3.player().pos);
+        assert!(world3.map().changed.is_empty());
+
+        delete(&path);
+        assert!(!units_path(&path).exists());
+    }
+
+    /// Savefile in the layout of the first versions: no save version, no random generator state,
+    /// units were saved on tiles and cloaks had no pockets
+    fn old_save(zombie_ai: &str) -> String {
+        let organ = r#"{"f":"f","a":30,"s":"3","l":true}"#;
+        let person = r#"{"a":{"a":30,"s":"1","h":"1","z":"3"},"m":{"n":"Old Man","g":"m","m":"r","l":true}}"#;
+        let brain = format!(
+            r#"{{"n":"brain","t":{{"human_brain":[{},{}]}},"o":[],"i":[]}}"#,
+            organ, person
+        );
+        let head = format!(
+            r#"{{"n":"head","t":{{"human_head":[{},"1","1","m"]}},"o":[],"i":[{}]}}"#,
+            organ, brain
         );
+        let torso = format!(
+            r#"{{"n":"torso","t":{{"human_torso":[{},"1","1","m"]}},"o":[{}],"i":[]}}"#,
+            organ, head
+        );
+        let body = format!(
+            r#"{{"p":[[{{"x":0,"y":0}},{}]],"w":[{{"cloak":{{}}}}]}}"#,
+            torso
+        );
+        let tile = |extra: &str| format!(r#"{{"t":{{"dirt":{{"v":"1"}}}},"i":[]{}}}"#, extra);
+        let mut tiles = vec![tile(""); Chunk::USIZE];
+        tiles[0] = tile(r#","u":[0]"#);
+        tiles[1] = tile(r#","u":[1]"#);
+        tiles[2] = r#"{"t":{"dirt":{"v":"1"}},"i":[{"cloak":{}}]}"#.to_string();
+        [
+            r#"{"name":"old","seed":"old","version":"0.1.0","time":{"secs_since_epoch":1650000000,"nanos_since_epoch":0},"current_tick":0}"#.to_string(),
+            r#"{"zoom":2}"#.to_string(),
+            r#"{"e":[],"i":0}"#.to_string(),
+            format!(
+                r#"{{"body":{},"pos":{{"x":0,"y":0}},"action":null,"vision":"East","wield":[{{"shovel":{{}}}}],"stamina":100,"soul":{{"Player":{}}}}}"#,
+                body, person
+            ),
+            format!(
+                r#"{{"body":{},"pos":{{"x":0,"y":1}},"action":null,"vision":"East","wield":[],"stamina":100,"soul":{{"Zombie":[{},{}]}}}}"#,
+                body, person, zombie_ai
+            ),
+            "/units".to_string(),
+            format!(r#"{{"pos":{{"x":0,"y":0}},"tiles":[{}]}}"#, tiles.join(",")),
+            "/chunks".to_string(),
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn test_old_save() {
+        let path = PathBuf::from("save/old.save");
+        std::fs::create_dir_all("save").unwrap();
+        std::fs::write(&path, old_save(r#"{"action":{"Skip":{}}}"#)).unwrap();
+        assert_eq!(0, load(&path).unwrap().save_version);
+
+        let mut world = load_world(&path).unwrap();
+        assert_eq!(SAVE_VERSION, world.meta.save_version);
+        assert_eq!(Point::new(0, 1), world.get_unit(1).pos);
+        let player = world.player();
+        assert!(!player.dead);
+        assert!(player.action_queue.is_empty());
+        assert_eq!(1, player.wield.len());
+        let pockets = player.body.wear[0].container().unwrap();
+        assert!(pockets.items.is_empty());
+        assert!(pockets.max_volume > 0);
+        assert!(world.map().get_tile(Point::new(0, 1)).units.contains(&1));
+        let cloak = &world.map().get_tile(Point::new(0, 2)).items[0];
+        assert!(cloak.container().is_some());
+
+        world.save(0).unwrap();
+        let world2 = load_world(&path).unwrap();
+        assert_eq!(Point::new(0, 1), world2.get_unit(1).pos);
+        assert!(world2.map().get_tile(Point::new(0, 1)).units.contains(&1));
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_old_save_with_broken_unit() {
+        let path = PathBuf::from("save/old_broken.save");
+        std::fs::create_dir_all("save").unwrap();
+        std::fs::write(&path, old_save(r#"{"action":{"Fly":{}}}"#)).unwrap();
+        let meta = load(&path).unwrap();
+        assert!(matches!(
+            check_units(&meta),
+            Err(Error::BrokenLine {
+                section: Section::Units,
+                line: 5,
+                ..
+            })
+        ));
+        let (world, lost) = salvage_world(&path).unwrap();
+        assert_eq!(1, world.loaded_units().count());
+        assert_eq!(1, lost.len());
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_region_backups() {
+        let path = PathBuf::from("save/region_backups.save");
+        let mut world = prepare_world();
+        world.meta.path = path.clone();
+        let far = Point::new(300, 0);
+        world.map().get_tile_mut(far).items.clear();
+        world.save(1).unwrap();
+        world.meta.current_tick += 10;
+        world
+            .map()
+            .get_tile_mut(far)
+            .items
+            .push(Shovel::new().into());
+        world.save(1).unwrap();
+
+        // savefile restored from the backup is older than the region
+        std::fs::copy(backup_path(&path, 1), &path).unwrap();
+        let mut world2 = load_world(&path).unwrap();
+        assert!(world2.map().get_tile(far).items.is_empty());
+        assert!(world2.map().lost.is_empty());
+        world2.save(1).unwrap();
+        let world3 = load_world(&path).unwrap();
+        assert!(world3.map().get_tile(far).items.is_empty());
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_unfinished_save() {
+        let path = PathBuf::from("save/unfinished.save");
+        let mut world = prepare_world();
+        world.meta.path = path.clone();
+        let far = Point::new(300, 0);
+        world.map().get_tile_mut(far).items.clear();
+        world.save(1).unwrap();
+        // the second save is made at the same tick
+        add_zombie(&mut world, Point::new(1, 0));
+        world
+            .map()
+            .get_tile_mut(far)
+            .items
+            .push(Shovel::new().into());
+        world.save(1).unwrap();
+
+        // crash after units are written, the log and the savefile are left from the first save
+        std::fs::copy(backup_path(&path, 1), &path).unwrap();
+        std::fs::copy(backup_path(&log_path(&path), 1), log_path(&path)).unwrap();
+        let world2 = load_world(&path).unwrap();
+        assert_eq!(1, world2.meta.saves);
+        assert_eq!(1, world2.loaded_units().count());
+        assert!(world2.map().get_tile(Point::new(1, 0)).units.is_empty());
+        assert!(world2.map().get_tile(far).items.is_empty());
+        assert!(world2.map().lost.is_empty());
+
+        // without backups nothing matches the savefile
+        std::fs::remove_file(backup_path(&units_path(&path), 1)).unwrap();
+        let error = load_world(&path).err().unwrap();
+        assert!(matches!(error, Error::NewerThanSavefile(Section::Units)));
+        let (world3, lost) = salvage_world(&path).unwrap();
+        assert_eq!(1, lost.len());
+        assert_eq!(2, world3.loaded_units().count());
+
+        delete(&path);
+    }
+
+    #[test]
+    fn test_backups() {
+        let path = PathBuf::from("save/backups.save");
+        let mut world = prepare_world();
+        world.meta.path = path.clone();
+        for _ in 0..4 {
+            world.save(2).unwrap();
+        }
+        assert!(path.is_file());
+        assert!(backup_path(&path, 1).is_file());
+        assert!(backup_path(&path, 2).is_file());
+        assert!(!backup_path(&path, 3).exists());
+        assert!(!tmp_path(&path).exists());
+        assert!(load_world(&path).is_ok());
 
         delete(&path);
+        assert!(!path.exists());
+        assert!(!backup_path(&path, 1).exists());
     }
 }
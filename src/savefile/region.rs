@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+
+use crate::game::ChunkPos;
+
+/// Width and height of a region in chunks
+pub const REGION_SIZE: i32 = 8;
+
+/// Group of chunks stored in one file
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RegionPos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl RegionPos {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<ChunkPos> for RegionPos {
+    fn from(pos: ChunkPos) -> Self {
+        Self::new(pos.x.div_euclid(REGION_SIZE), pos.y.div_euclid(REGION_SIZE))
+    }
+}
+
+/// Directory with units, log and regions of the world, near its savefile
+pub fn world_dir(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+pub fn units_path(path: &Path) -> PathBuf {
+    world_dir(path).join("units")
+}
+
+pub fn log_path(path: &Path) -> PathBuf {
+    world_dir(path).join("log")
+}
+
+pub fn regions_dir(path: &Path) -> PathBuf {
+    world_dir(path).join("regions")
+}
+
+/// Changed chunks of regions unloaded between saves, the next save moves them to region files
+pub fn unsaved_dir(regions_dir: &Path) -> PathBuf {
+    regions_dir.join("unsaved")
+}
+
+pub fn region_path(regions_dir: &Path, region: RegionPos) -> PathBuf {
+    regions_dir.join(format!("{}.{}.region", region.x, region.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::game::ChunkPos;
+
+    use super::{region_path, regions_dir, RegionPos};
+
+    #[test]
+    fn test_region_pos() {
+        assert_eq!(RegionPos::new(0, 0), ChunkPos::new(0, 7).into());
+        assert_eq!(RegionPos::new(1, 0), ChunkPos::new(8, 0).into());
+        assert_eq!(RegionPos::new(-1, -1), ChunkPos::new(-1, -8).into());
+        assert_eq!(RegionPos::new(-2, 0), ChunkPos::new(-9, 0).into());
+    }
+
+    #[test]
+    fn test_region_path() {
+        let dir = regions_dir(Path::new("save/test.save"));
+        assert_eq!(Path::new("save/test/regions"), dir);
+        assert_eq!(
+            Path::new("save/test/regions/-1.2.region"),
+            region_path(&dir, RegionPos::new(-1, 2))
+        );
+    }
+}
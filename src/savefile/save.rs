@@ -2,7 +2,14 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use super::{Meta, SAVEFILES_FOLDER};
+use serde_json::json;
+
+use crate::game::Chunk;
+
+use super::{
+    region::{log_path, region_path, regions_dir, units_path, unsaved_dir, RegionPos},
+    Meta, SAVEFILES_FOLDER, SAVE_VERSION,
+};
 
 #[derive(Debug)]
 pub enum Error {
@@ -11,6 +18,16 @@ pub enum Error {
     FileExists,
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::System(e) => write!(f, "Can't write the file: {}", e),
+            Error::Serialize(e) => write!(f, "Can't serialize: {}", e),
+            Error::FileExists => f.write_str("File already exists"),
+        }
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(e: serde_json::Error) -> Self {
         Error::Serialize(e.to_string())
@@ -36,10 +53,110 @@ pub fn create(name: &str, seed: &str) -> Result<PathBuf, Error> {
         .map(|_| path)
 }
 
-pub fn save(path: &Path, data: &str) -> Result<(), Error> {
+/// Serialized world, every part goes to its own file
+pub struct WorldData {
+    /// Meta, view and random generator
+    pub main: String,
+    /// Header and units one per line
+    pub units: String,
+    /// Header and the log
+    pub log: String,
+    /// Only regions with chunks changed since the last save
+    pub regions: Vec<(RegionPos, String)>,
+}
+
+/// Writes regions, units and log first and the savefile with meta last.
+/// Every file keeps `backups` rotating backups. Files are marked with the number of the save,
+/// so units and log left from an unfinished save or regions newer than a savefile
+/// restored from a backup are not mixed with the savefile.
+pub fn save(path: &Path, data: &WorldData, backups: usize) -> Result<(), Error> {
     make_dir()?;
-    let mut file = File::create(path).map_err(Error::from)?;
-    file.write_all(data.as_bytes()).map_err(Into::into)
+    let regions = regions_dir(path);
+    std::fs::create_dir_all(&regions).map_err(Error::from)?;
+    for (region, data) in &data.regions {
+        write_file(&region_path(&regions, *region), data, backups)?;
+    }
+    write_file(&units_path(path), &data.units, backups)?;
+    write_file(&log_path(path), &data.log, backups)?;
+    write_file(path, &data.main, backups)
+}
+
+/// First line of units, log and region files with the layout version, the tick
+/// and the number of the save that wrote them
+pub fn header(tick: u128, save: u64) -> Result<String, Error> {
+    let tick = u64::try_from(tick).unwrap_or(u64::MAX);
+    serde_json::to_string(&json!({ "save_version": SAVE_VERSION, "tick": tick, "save": save }))
+        .map_err(Error::from)
+}
+
+/// Header line, chunks of the region one per line
+pub fn region_data<'a, I: Iterator<Item = &'a Chunk>>(
+    chunks: I,
+    header: &str,
+) -> Result<String, Error> {
+    let mut data = header.to_string();
+    for chunk in chunks {
+        data.push('\n');
+        data.push_str(serde_json::to_string(chunk)?.as_str());
+    }
+    Ok(data)
+}
+
+/// Changed chunks of the unloaded region are kept aside, region files are written
+/// only together with the rest of the world
+pub fn save_unloaded(regions_dir: &Path, region: RegionPos, data: &str) -> Result<(), Error> {
+    let dir = unsaved_dir(regions_dir);
+    std::fs::create_dir_all(&dir).map_err(Error::from)?;
+    write_file(&region_path(&dir, region), data, 0)
+}
+
+/// Calls after the save, unloaded chunks are in region files now
+pub fn clear_unloaded(regions_dir: &Path) {
+    std::fs::remove_dir_all(unsaved_dir(regions_dir)).ok();
+}
+
+/// Writes to a temporary file and renames it over the old one,
+/// so a crash in the middle of writing can't destroy the world.
+/// The old file becomes the newest of `backups` rotating backups.
+fn write_file(path: &Path, data: &str, backups: usize) -> Result<(), Error> {
+    let tmp = tmp_path(path);
+    let mut file = File::create(&tmp).map_err(Error::from)?;
+    file.write_all(data.as_bytes()).map_err(Error::from)?;
+    file.sync_all().map_err(Error::from)?;
+    drop(file);
+    if backups > 0 && path.is_file() {
+        rotate_backups(path, backups)?;
+    }
+    std::fs::rename(&tmp, path).map_err(Into::into)
+}
+
+/// `world.save.bak1` is the newest backup
+pub fn backup_path(path: &Path, i: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".bak{}", i));
+    PathBuf::from(name)
+}
+
+/// Copy of a file that can't be read completely, e.g. `0.0.region.broken`
+pub fn broken_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".broken");
+    PathBuf::from(name)
+}
+
+pub fn tmp_path(path: &Path) -> PathBuf {
+    path.with_extension("tmp")
+}
+
+fn rotate_backups(path: &Path, backups: usize) -> Result<(), Error> {
+    for i in (1..backups).rev() {
+        let older = backup_path(path, i);
+        if older.is_file() {
+            std::fs::rename(&older, backup_path(path, i + 1)).map_err(Error::from)?;
+        }
+    }
+    std::fs::copy(path, backup_path(path, 1)).map_err(Error::from)?;
+    Ok(())
 }
 
 fn make_dir() -> Result<(), Error> {
@@ -1,7 +1,8 @@
 use enum_dispatch::enum_dispatch;
 
 use super::implements::{
-    Animate, Digging, Dropping, Examining, Observing, Reading, Walking, Wielding,
+    Animate, Attaching, Commanding, Digging, Dismembering, Dressing, Dropping, Examining, Fighting,
+    Observing, PickingUp, PuttingIn, Reading, TakingOut, Traveling, Undressing, Walking, Wielding,
 };
 
 #[enum_dispatch(GameModeImpl)]
@@ -14,4 +15,14 @@ pub enum GameMode {
     Observing,
     Reading,
     Animate,
+    Dismembering,
+    Attaching,
+    Fighting,
+    Commanding,
+    Traveling,
+    PickingUp,
+    PuttingIn,
+    TakingOut,
+    Dressing,
+    Undressing,
 }
@@ -7,7 +7,11 @@ use crate::game::World;
 
 use super::{
     super::{implements::GameScene, SomeTransitions},
-    implements::{Animate, Digging, Dropping, Examining, Observing, Reading, Walking, Wielding},
+    implements::{
+        Animate, Attaching, Commanding, Digging, Dismembering, Dressing, Dropping, Examining,
+        Fighting, Observing, PickingUp, PuttingIn, Reading, TakingOut, Traveling, Undressing,
+        Walking, Wielding,
+    },
     GameMode,
 };
 
@@ -0,0 +1,147 @@
+use geometry::{Direction, Point, DIR9};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{
+        actions::implements::Attach,
+        bodies::{Body, BodyPartPath},
+        map::items::BodyPart,
+        Item, World,
+    },
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector,
+};
+
+pub struct Attaching {
+    selected: Option<Direction>,
+    slots: Option<PartSelector<(BodyPartPath, bool)>>,
+}
+
+impl Attaching {
+    pub fn new() -> Self {
+        Self {
+            selected: None,
+            slots: None,
+        }
+    }
+
+    fn wielded_part(world: &World) -> Option<&BodyPart> {
+        world.player().wield.iter().find_map(|i| match i {
+            Item::BodyPart(part) => Some(part),
+            _ => None,
+        })
+    }
+
+    fn slots(body: &Body, part: &BodyPart) -> PartSelector<(BodyPartPath, bool)> {
+        let mut options = Vec::new();
+        for path in body.paths() {
+            let parent = body.get_part(&path).unwrap();
+            let name = body.describe(&path);
+            for inside in [false, true] {
+                let mut caption =
+                    format!("{} ({})", name, if inside { "inside" } else { "outside" });
+                if !parent.fits(part, inside) {
+                    caption.push_str(" - doesn't fit");
+                }
+                options.push(((path.clone(), inside), caption));
+            }
+        }
+        PartSelector::new(options)
+    }
+}
+
+impl Default for Attaching {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Attaching {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR9.iter()
+                .copied()
+                .filter(|d| {
+                    let pos = world.player().pos + *d;
+                    let mut map = world.map();
+                    let tile = map.get_tile(pos);
+                    tile.items.iter().any(|i| matches!(i, Item::Corpse(..)))
+                        || tile.units.iter().any(|&i| world.get_unit(i).is_zombie())
+                })
+                .map(|d| (d.into(), Colors::LIGHT_YELLOW))
+                .collect()
+        }
+    }
+
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        if Self::wielded_part(world).is_some() {
+            Ok(())
+        } else {
+            Err("You have to hold a body part to attach it".to_string())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(slots) = &mut self.slots {
+            if let Some((parent, inside)) = slots.update(ctx, game) {
+                let dir = self.selected.unwrap();
+                game.try_start_action(
+                    Attach {
+                        dir,
+                        parent,
+                        inside,
+                    }
+                    .into(),
+                );
+                game.modes.pop();
+            }
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if let Some(dir) = self.selected {
+            let world = game.world.borrow();
+            let pos = world.player().pos + dir;
+            let slots = Self::wielded_part(&world).and_then(|part| {
+                let mut map = world.map();
+                let tile = map.get_tile(pos);
+                let zombie = tile
+                    .units
+                    .iter()
+                    .map(|&i| world.get_unit(i))
+                    .find(|unit| unit.is_zombie());
+                if let Some(unit) = zombie {
+                    Some(Self::slots(&unit.body, part))
+                } else {
+                    tile.items.iter().find_map(|i| match i {
+                        Item::Corpse(corpse) => Some(Self::slots(&corpse.body, part)),
+                        _ => None,
+                    })
+                }
+            });
+            drop(world);
+            if slots.is_some() {
+                self.slots = slots;
+            } else {
+                game.log
+                    .log("There is nothing to attach it to", Colors::LIGHT_CORAL);
+                game.modes.pop();
+            }
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(slots) = &mut self.slots {
+            slots.draw(ctx);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use geometry::{Direction, Point, DIR8};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{ai::Order, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector, TileSelector,
+};
+
+pub struct Commanding {
+    selected: Option<Direction>,
+    unit_id: Option<usize>,
+    orders: Option<PartSelector<Order>>,
+    order: Option<Order>,
+    targets: Vec<Point>,
+    tiles: TileSelector,
+}
+
+impl Commanding {
+    pub fn new() -> Self {
+        Self {
+            selected: None,
+            unit_id: None,
+            orders: None,
+            order: None,
+            targets: Vec::new(),
+            tiles: TileSelector::default(),
+        }
+    }
+
+    fn give_order(&self, game: &mut GameScene, order: Order) {
+        let mut world = game.world.borrow_mut();
+        let unit = world.get_unit_mut(self.unit_id.unwrap());
+        if unit.give_order(order) {
+            let name = unit.name_for_actions();
+            drop(world);
+            game.log.log(
+                format!("You gave an order to {}", name),
+                Colors::WHITE_SMOKE,
+            );
+        }
+    }
+}
+
+impl Default for Commanding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Commanding {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        let center = world.player().pos;
+        if self.order.is_some() {
+            let mut cursors: Vec<(Point, Color)> = self
+                .targets
+                .iter()
+                .map(|t| {
+                    (
+                        Point::new(t.x - center.x, t.y - center.y),
+                        Colors::LIGHT_YELLOW,
+                    )
+                })
+                .collect();
+            cursors.push((self.tiles.cursor, Colors::LIME));
+            cursors
+        } else if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR8.iter()
+                .copied()
+                .filter(|d| world.zombie_at(center + *d).is_some())
+                .map(|d| (d.into(), Colors::LIGHT_YELLOW))
+                .collect()
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(order) = self.order {
+            if let Some(delta) = self.tiles.update(ctx) {
+                let pos = game.world.borrow().player().pos + delta;
+                self.targets.push(pos);
+                if self.targets.len() == order.targets_needed() {
+                    self.give_order(game, order.with_targets(&self.targets));
+                    game.modes.pop();
+                }
+            }
+        } else if let Some(orders) = &mut self.orders {
+            if let Some(order) = orders.update(ctx, game) {
+                if order.targets_needed() == 0 {
+                    self.give_order(game, order);
+                    game.modes.pop();
+                } else {
+                    self.order = Some(order);
+                    self.tiles = TileSelector::new(self.selected.unwrap().into());
+                    game.log.log(
+                        "Choose a tile with direction keys and press Enter",
+                        Colors::WHITE_SMOKE,
+                    );
+                }
+            }
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if let Some(dir) = self.selected {
+            let world = game.world.borrow();
+            let unit_id = world.zombie_at(world.player().pos + dir);
+            let error = match unit_id {
+                None => Some("There is no one to command".to_string()),
+                Some(i) if !world.get_unit(i).body.capabilities().brain => Some(format!(
+                    "{} has no brain to understand you",
+                    world.get_unit(i).name_for_actions()
+                )),
+                Some(_) => None,
+            };
+            drop(world);
+            if let Some(error) = error {
+                game.log.log(error, Colors::LIGHT_CORAL);
+                game.modes.pop();
+            } else {
+                self.unit_id = unit_id;
+                self.orders = Some(PartSelector::new(
+                    Order::all()
+                        .into_iter()
+                        .map(|o| (o, o.name().to_string()))
+                        .collect(),
+                ));
+            }
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(orders) = &mut self.orders {
+            if self.order.is_none() {
+                orders.draw(ctx);
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use geometry::{Direction, Point, DIR9};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{
+        actions::implements::Dismember,
+        bodies::BodyPartPath,
+        map::item::{ItemInteract, ItemTag},
+        Item, World,
+    },
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector,
+};
+
+pub struct Dismembering {
+    selected: Option<Direction>,
+    parts: Option<PartSelector<BodyPartPath>>,
+}
+
+impl Dismembering {
+    pub fn new() -> Self {
+        Self {
+            selected: None,
+            parts: None,
+        }
+    }
+}
+
+impl Default for Dismembering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Dismembering {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR9.iter()
+                .copied()
+                .filter(|d| {
+                    let pos = world.player().pos + *d;
+                    world
+                        .map()
+                        .get_tile(pos)
+                        .items
+                        .iter()
+                        .any(|i| matches!(i, Item::Corpse(..)))
+                })
+                .map(|d| (d.into(), Colors::LIGHT_YELLOW))
+                .collect()
+        }
+    }
+
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        if world
+            .player()
+            .wield
+            .iter()
+            .any(|i| i.tags().contains(&ItemTag::Butch))
+        {
+            Ok(())
+        } else {
+            Err("You can't dismember without a knife or an axe".to_string())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(parts) = &mut self.parts {
+            if let Some(path) = parts.update(ctx, game) {
+                let dir = self.selected.unwrap();
+                game.try_start_action(Dismember { dir, path }.into());
+                game.modes.pop();
+            }
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if let Some(dir) = self.selected {
+            let world = game.world.borrow();
+            let pos = world.player().pos + dir;
+            let parts = world
+                .map()
+                .get_tile(pos)
+                .items
+                .iter()
+                .find_map(|i| match i {
+                    Item::Corpse(corpse) => Some(PartSelector::new(
+                        corpse
+                            .body
+                            .detachable_paths()
+                            .into_iter()
+                            .map(|path| {
+                                let caption = corpse.body.describe(&path);
+                                (path, caption)
+                            })
+                            .collect(),
+                    )),
+                    _ => None,
+                })
+                .filter(|parts| !parts.is_empty());
+            drop(world);
+            if parts.is_some() {
+                self.parts = parts;
+            } else {
+                game.log
+                    .log("There is nothing to cut off here", Colors::LIGHT_CORAL);
+                game.modes.pop();
+            }
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(parts) = &mut self.parts {
+            parts.draw(ctx);
+        }
+    }
+}
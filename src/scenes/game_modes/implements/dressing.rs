@@ -0,0 +1,96 @@
+use geometry::{Direction, Point, DIR9};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{
+        actions::implements::Wear,
+        map::item::{ItemInteract, ItemView},
+        World,
+    },
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector,
+};
+
+pub struct Dressing {
+    selected: Option<Direction>,
+    items: Option<PartSelector<usize>>,
+}
+
+impl Dressing {
+    pub fn new() -> Self {
+        Self {
+            selected: None,
+            items: None,
+        }
+    }
+}
+
+impl Default for Dressing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Dressing {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR9.iter()
+                .copied()
+                .filter(|d| {
+                    d.is_here()
+                        || world.inspect_body_at(world.player().pos + *d, |body| body.is_some())
+                })
+                .map(|d| (d.into(), Colors::LIGHT_YELLOW))
+                .collect()
+        }
+    }
+
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        if world.player().wield.iter().any(ItemInteract::is_wearable) {
+            Ok(())
+        } else {
+            Err("You have nothing to wear in your hands".to_string())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(items) = &mut self.items {
+            if let Some(item_id) = items.update(ctx, game) {
+                let dir = self.selected.unwrap();
+                game.try_start_action(Wear { item_id, dir }.into());
+                game.modes.pop();
+            }
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if self.selected.is_some() {
+            let items = game
+                .world
+                .borrow()
+                .player()
+                .wield
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.is_wearable())
+                .map(|(i, item)| (i, format!("Put on the {}", item.name())))
+                .collect();
+            self.items = Some(PartSelector::new(items));
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(items) = &mut self.items {
+            items.draw(ctx);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use geometry::{Direction, Point, DIR8};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{actions::implements::Attack, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl,
+};
+
+pub struct Fighting {
+    selected: Option<Direction>,
+}
+
+impl Fighting {
+    pub fn new() -> Self {
+        Self { selected: None }
+    }
+}
+
+impl Default for Fighting {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Fighting {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR8.iter()
+                .copied()
+                .filter(|d| {
+                    let pos = world.player().pos + *d;
+                    !world.map().get_tile(pos).units.is_empty()
+                })
+                .map(|d| (d.into(), Colors::LIGHT_CORAL))
+                .collect()
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if let Some(dir) = self.selected {
+            game.try_start_action(Attack { dir }.into());
+            game.modes.pop();
+        }
+        None
+    }
+}
@@ -1,17 +1,37 @@
 pub use animate::Animate;
+pub use attaching::Attaching;
+pub use commanding::Commanding;
 pub use digging::Digging;
+pub use dismembering::Dismembering;
+pub use dressing::Dressing;
 pub use dropping::Dropping;
 pub use examining::Examining;
+pub use fighting::Fighting;
 pub use observing::Observing;
+pub use picking_up::PickingUp;
+pub use putting_in::PuttingIn;
 pub use reading::Reading;
+pub use taking_out::TakingOut;
+pub use traveling::Traveling;
+pub use undressing::Undressing;
 pub use walking::Walking;
 pub use wielding::Wielding;
 
 mod animate;
+mod attaching;
+mod commanding;
 mod digging;
+mod dismembering;
+mod dressing;
 mod dropping;
 mod examining;
+mod fighting;
 mod observing;
+mod picking_up;
+mod putting_in;
 mod reading;
+mod taking_out;
+mod traveling;
+mod undressing;
 mod walking;
 mod wielding;
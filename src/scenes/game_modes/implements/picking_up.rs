@@ -0,0 +1,67 @@
+use geometry::{Direction, Point, DIR9};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{actions::implements::PickUp, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl,
+};
+
+pub struct PickingUp {
+    selected: Option<Direction>,
+}
+
+impl PickingUp {
+    pub fn new() -> Self {
+        Self { selected: None }
+    }
+}
+
+impl Default for PickingUp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for PickingUp {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR9.iter()
+                .copied()
+                .filter(|d| {
+                    let pos = world.player().pos + *d;
+                    !world.map().get_tile(pos).items.is_empty()
+                })
+                .map(|d| (d.into(), Colors::WHITE_SMOKE))
+                .collect()
+        }
+    }
+
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        if world.player().containers().next().is_some() {
+            Ok(())
+        } else {
+            Err("You have nothing to put items in".to_string())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if let Some(dir) = self.selected {
+            game.try_start_action(PickUp { dir }.into());
+            game.modes.pop();
+        }
+        None
+    }
+}
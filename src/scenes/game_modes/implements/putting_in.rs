@@ -0,0 +1,67 @@
+use tetra::{input::Key, Context};
+
+use crate::{
+    game::{actions::implements::PutIn, map::item::ItemView, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector,
+};
+
+pub struct PuttingIn {
+    items: Option<PartSelector<usize>>,
+}
+
+impl PuttingIn {
+    pub fn new() -> Self {
+        Self { items: None }
+    }
+}
+
+impl Default for PuttingIn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for PuttingIn {
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        if world.player().wield.is_empty() {
+            Err("You have nothing in your hands".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+            return None;
+        }
+        let items = self.items.get_or_insert_with(|| {
+            PartSelector::new(
+                game.world
+                    .borrow()
+                    .player()
+                    .wield
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| (i, format!("Put away the {}", item.name())))
+                    .collect(),
+            )
+        });
+        if let Some(item_id) = items.update(ctx, game) {
+            game.try_start_action(PutIn { item_id }.into());
+            game.modes.pop();
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(items) = &mut self.items {
+            items.draw(ctx);
+        }
+    }
+}
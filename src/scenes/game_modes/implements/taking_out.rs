@@ -0,0 +1,77 @@
+use tetra::{input::Key, Context};
+
+use crate::{
+    game::{actions::implements::TakeOut, map::item::ItemView, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector,
+};
+
+pub struct TakingOut {
+    items: Option<PartSelector<(usize, usize)>>,
+}
+
+impl TakingOut {
+    pub fn new() -> Self {
+        Self { items: None }
+    }
+}
+
+impl Default for TakingOut {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for TakingOut {
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        let player = world.player();
+        if player.free_hands() == 0 {
+            Err("You have no free hands".to_string())
+        } else if player.containers().all(|c| c.items.is_empty()) {
+            Err("You have nothing to take out".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+            return None;
+        }
+        let items = self.items.get_or_insert_with(|| {
+            let world = game.world.borrow();
+            let mut options = Vec::new();
+            for (container_id, container) in world.player().containers().enumerate() {
+                for (item_id, item) in container.items.iter().enumerate() {
+                    options.push((
+                        (container_id, item_id),
+                        format!("Take out the {}", item.name()),
+                    ));
+                }
+            }
+            PartSelector::new(options)
+        });
+        if let Some((container_id, item_id)) = items.update(ctx, game) {
+            game.try_start_action(
+                TakeOut {
+                    container_id,
+                    item_id,
+                }
+                .into(),
+            );
+            game.modes.pop();
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(items) = &mut self.items {
+            items.draw(ctx);
+        }
+    }
+}
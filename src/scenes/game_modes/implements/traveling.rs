@@ -0,0 +1,106 @@
+use geometry::{Direction, Point};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{actions::implements::Walk, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, TileSelector,
+};
+
+/// How many tiles are looked at while searching for a route
+const ROUTE_BUDGET: usize = 10_000;
+
+pub struct Traveling {
+    tiles: TileSelector,
+    route: Option<Vec<Point>>,
+}
+
+impl Traveling {
+    pub fn new() -> Self {
+        Self {
+            tiles: TileSelector::default(),
+            route: None,
+        }
+    }
+
+    fn start(&mut self, game: &mut GameScene, delta: Point) {
+        let world = game.world.borrow();
+        let from = world.player().pos;
+        let to = from + delta;
+        if !world.is_known(to) {
+            drop(world);
+            game.log
+                .log("You don't know how to get there", Colors::LIGHT_CORAL);
+            return;
+        }
+        let path = world.player_route(to, ROUTE_BUDGET);
+        drop(world);
+        let mut walks = Vec::with_capacity(path.steps.len());
+        let mut pos = from;
+        for &step in &path.steps {
+            walks.push(Walk {
+                dir: Direction::from_delta(step.x - pos.x, step.y - pos.y),
+            });
+            pos = step;
+        }
+        if let Some(first) = walks.first() {
+            game.try_rotate_player(first.dir);
+            if game.try_start_action((*first).into()) {
+                game.world
+                    .borrow_mut()
+                    .player_mut()
+                    .action_queue
+                    .extend(walks.iter().skip(1).map(|&w| w.into()));
+                self.route = Some(path.steps);
+                return;
+            }
+        } else {
+            game.log
+                .log("You can't find a way there", Colors::LIGHT_CORAL);
+        }
+        game.modes.pop();
+    }
+}
+
+impl Default for Traveling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Traveling {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(route) = &self.route {
+            let center = world.player().pos;
+            let left = world.player().action_queue.len() + 1;
+            route[route.len().saturating_sub(left)..]
+                .iter()
+                .map(|p| {
+                    (
+                        Point::new(p.x - center.x, p.y - center.y),
+                        Colors::LIGHT_YELLOW,
+                    )
+                })
+                .collect()
+        } else {
+            vec![(self.tiles.cursor, Colors::LIME)]
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if self.route.is_some() {
+            // player arrived or was interrupted
+            game.modes.pop();
+        } else if let Some(delta) = self.tiles.update(ctx) {
+            self.start(game, delta);
+        }
+        None
+    }
+}
@@ -0,0 +1,110 @@
+use geometry::{Direction, Point, DIR9};
+use tetra::{graphics::Color, input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    game::{actions::implements::TakeOff, bodies::Body, map::item::ItemView, World},
+    input,
+};
+
+use super::super::{
+    super::{implements::GameScene, SomeTransitions},
+    GameModeImpl, PartSelector,
+};
+
+pub struct Undressing {
+    selected: Option<Direction>,
+    items: Option<PartSelector<usize>>,
+}
+
+impl Undressing {
+    pub fn new() -> Self {
+        Self {
+            selected: None,
+            items: None,
+        }
+    }
+
+    fn items(body: Option<&Body>) -> Vec<(usize, String)> {
+        body.map_or_else(Vec::new, |body| {
+            body.wear
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (i, format!("Take off the {}", item.name())))
+                .collect()
+        })
+    }
+}
+
+impl Default for Undressing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeImpl for Undressing {
+    fn cursors(&self, world: &World) -> Vec<(Point, Color)> {
+        if let Some(selected) = self.selected {
+            vec![(selected.into(), Colors::LIME)]
+        } else {
+            DIR9.iter()
+                .copied()
+                .filter(|d| {
+                    if d.is_here() {
+                        !world.player().body.wear.is_empty()
+                    } else {
+                        world.inspect_body_at(world.player().pos + *d, |body| {
+                            body.map_or(false, |body| !body.wear.is_empty())
+                        })
+                    }
+                })
+                .map(|d| (d.into(), Colors::LIGHT_YELLOW))
+                .collect()
+        }
+    }
+
+    fn can_push(&self, world: &World) -> Result<(), String> {
+        if world.player().free_hands() > 0 {
+            Ok(())
+        } else {
+            Err("You have no free hands".to_string())
+        }
+    }
+
+    fn update(&mut self, ctx: &mut Context, game: &mut GameScene) -> SomeTransitions {
+        if input::is_key_pressed(ctx, Key::Escape) {
+            game.modes.pop();
+        } else if let Some(items) = &mut self.items {
+            if let Some(item_id) = items.update(ctx, game) {
+                let dir = self.selected.unwrap();
+                game.try_start_action(TakeOff { item_id, dir }.into());
+                game.modes.pop();
+            }
+        } else if let Some(dir) = input::get_direction_keys_down(ctx) {
+            self.selected = Some(dir);
+            game.try_rotate_player(dir);
+        } else if let Some(dir) = self.selected {
+            let world = game.world.borrow();
+            let items = if dir.is_here() {
+                Self::items(Some(&world.player().body))
+            } else {
+                world.inspect_body_at(world.player().pos + dir, Self::items)
+            };
+            drop(world);
+            if items.is_empty() {
+                game.log
+                    .log("There is nothing to take off", Colors::LIGHT_CORAL);
+                game.modes.pop();
+            } else {
+                self.items = Some(PartSelector::new(items));
+            }
+        }
+        None
+    }
+
+    fn draw(&mut self, ctx: &mut Context, _game: &mut GameScene) {
+        if let Some(items) = &mut self.items {
+            items.draw(ctx);
+        }
+    }
+}
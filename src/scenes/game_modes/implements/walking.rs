@@ -7,18 +7,18 @@ use tetra::{
 };
 
 use crate::{
-    colors::Colors,
-    game::{
-        actions::implements::{Drop, Skip, Walk},
-        map::item::ItemView,
-    },
+    game::actions::implements::{Drop, Skip, Walk},
     input,
     settings::Settings,
 };
 
 use super::super::{
     super::{implements::GameScene, Scene, SomeTransitions, Transition},
-    implements::{Animate, Digging, Dropping, Examining, Observing, Reading, Wielding},
+    implements::{
+        Animate, Attaching, Commanding, Digging, Dismembering, Dressing, Dropping, Examining,
+        Fighting, Observing, PickingUp, PuttingIn, Reading, TakingOut, Traveling, Undressing,
+        Wielding,
+    },
     GameModeImpl,
 };
 
@@ -68,6 +68,12 @@ impl GameModeImpl for Walking {
         } else if input::is_key_with_mod_pressed(ctx, Key::W) {
             game.push_mode(Wielding::new().into());
             None
+        } else if input::is_key_with_mod_pressed(ctx, (Key::W, KeyModifier::Shift)) {
+            game.push_mode(Dressing::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, Key::U) {
+            game.push_mode(Undressing::new().into());
+            None
         } else if input::is_key_with_mod_pressed(ctx, (Key::C, KeyModifier::Shift)) {
             game.log.clear();
             None
@@ -83,24 +89,34 @@ impl GameModeImpl for Walking {
         } else if input::is_key_with_mod_pressed(ctx, (Key::Num2, KeyModifier::Shift)) {
             Some(vec![Transition::Push(Scene::BodyView(0))])
         } else if input::is_key_with_mod_pressed(ctx, Key::I) {
-            // TODO: inventory game scene
-            let items: Vec<String> = game
-                .world
-                .borrow()
-                .player()
-                .body
-                .wear
-                .iter()
-                .map(ItemView::name)
-                .collect();
-            game.log.log(
-                format!("You wear: {}", items.join(", ")),
-                Colors::WHITE_SMOKE,
-            );
+            Some(vec![Transition::Push(Scene::Inventory)])
+        } else if input::is_key_with_mod_pressed(ctx, Key::P) {
+            game.push_mode(PickingUp::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, (Key::P, KeyModifier::Shift)) {
+            game.push_mode(PuttingIn::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, Key::O) {
+            game.push_mode(TakingOut::new().into());
             None
         } else if input::is_key_with_mod_pressed(ctx, Key::A) {
             game.push_mode(Animate::new().into());
             None
+        } else if input::is_key_with_mod_pressed(ctx, Key::B) {
+            game.push_mode(Dismembering::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, (Key::B, KeyModifier::Shift)) {
+            game.push_mode(Attaching::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, Key::F) {
+            game.push_mode(Fighting::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, Key::C) {
+            game.push_mode(Commanding::new().into());
+            None
+        } else if input::is_key_with_mod_pressed(ctx, Key::T) {
+            game.push_mode(Traveling::new().into());
+            None
         } else if let Some(dir) = input::get_direction_keys_down(ctx) {
             let now = Instant::now();
             if now.duration_since(self.last_walk).subsec_millis()
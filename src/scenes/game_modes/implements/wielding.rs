@@ -45,8 +45,10 @@ impl GameModeImpl for Wielding {
     }
 
     fn can_push(&self, world: &World) -> Result<(), String> {
-        // TODO: hands counting
-        if world.player().wield.is_empty() {
+        let hands = world.player().body.capabilities().hands as usize;
+        if hands == 0 {
+            Err("You have no hands to hold anything".to_string())
+        } else if world.player().wield.len() < hands {
             Ok(())
         } else {
             Err(format!(
@@ -1,6 +1,10 @@
 pub use game_mode::GameMode;
 pub use game_mode_impl::GameModeImpl;
+pub use part_selector::PartSelector;
+pub use tile_selector::TileSelector;
 
 mod game_mode;
 mod game_mode_impl;
 pub mod implements;
+mod part_selector;
+mod tile_selector;
@@ -0,0 +1,69 @@
+use tetra::{input::Key, Context};
+
+use crate::{
+    colors::Colors,
+    input,
+    ui::{Draw, Label, Position, Vertical},
+};
+
+use super::super::implements::GameScene;
+
+/// List of body parts (or slots) to choose one with Up/Down keys and confirm with Enter
+pub struct PartSelector<T: Clone> {
+    options: Vec<(T, String)>,
+    selected: usize,
+    label: Option<Label>,
+}
+
+impl<T: Clone> PartSelector<T> {
+    /// Every option is a value and its caption
+    pub fn new(options: Vec<(T, String)>) -> Self {
+        Self {
+            options,
+            selected: 0,
+            label: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    /// Returns selected value when player confirms the choice
+    pub fn update(&mut self, ctx: &mut Context, game: &GameScene) -> Option<T> {
+        if self.options.is_empty() {
+            return None;
+        }
+        let len = self.options.len();
+        let mut changed = self.label.is_none();
+        if input::is_key_pressed(ctx, Key::Up) {
+            self.selected = (self.selected + len - 1) % len;
+            changed = true;
+        } else if input::is_key_pressed(ctx, Key::Down) {
+            self.selected = (self.selected + 1) % len;
+            changed = true;
+        } else if input::is_key_pressed(ctx, Key::Enter) {
+            return Some(self.options[self.selected].0.clone());
+        }
+        if changed {
+            let text = format!("[Up/Down] {} [Enter]", self.options[self.selected].1);
+            let label = self.label.get_or_insert_with(|| {
+                Label::new(
+                    "",
+                    game.assets.fonts.header2.clone(),
+                    Colors::LIGHT_YELLOW,
+                    Position::horizontal_center(0.0, Vertical::ByTop { y: 40.0 }),
+                )
+            });
+            label.update(text, ctx, game.window_size);
+        }
+
+        None
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context) {
+        if let Some(label) = &mut self.label {
+            label.draw(ctx);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::Instant;
+
+use geometry::Point;
+use tetra::{
+    input::{Key, KeyModifier},
+    Context,
+};
+
+use crate::{input, settings::Settings};
+
+/// Cursor to choose a tile around the player with direction keys and confirm with Enter
+pub struct TileSelector {
+    /// Position relative to the player
+    pub cursor: Point,
+    last_shift: Instant,
+}
+
+impl TileSelector {
+    pub fn new(cursor: Point) -> Self {
+        Self {
+            cursor,
+            last_shift: Instant::now(),
+        }
+    }
+
+    /// Returns position relative to the player when player confirms the choice
+    pub fn update(&mut self, ctx: &mut Context) -> Option<Point> {
+        if input::is_key_pressed(ctx, Key::Enter) {
+            return Some(self.cursor);
+        }
+        if let Some(dir) = input::get_direction_keys_down(ctx) {
+            let now = Instant::now();
+            if now.duration_since(self.last_shift).subsec_millis()
+                > Settings::instance().game.repeat_interval
+                || input::is_key_modifier_down(ctx, KeyModifier::Shift)
+            {
+                self.last_shift = now;
+                self.cursor += dir;
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for TileSelector {
+    fn default() -> Self {
+        Self::new(Point::default())
+    }
+}
@@ -1,4 +1,4 @@
-use geometry::{Point, Vec2};
+use geometry::Vec2;
 use tetra::{
     graphics::{
         mesh::{Mesh, ShapeStyle},
@@ -46,19 +46,23 @@ impl BodyView {
             },
             &app.assets,
         );
+        let (name, age) = if let Some(person) = avatar.person() {
+            (
+                person.mind.name.clone(),
+                format!("({}, {})", person.age_name(), person.appearance.age),
+            )
+        } else {
+            (avatar.name_for_actions(), String::new())
+        };
         let mut name = Label::new(
-            avatar.person().mind.name.clone(),
+            name,
             app.assets.fonts.header2.clone(),
             Colors::LIGHT_GOLDEN_ROD_YELLOW,
             Position::by_left_top(20.0, 20.0),
         );
         let name_size = name.calc_size(ctx);
         let gender = Label::new(
-            format!(
-                "({}, {})",
-                avatar.person().age_name(),
-                avatar.person().appearance.age
-            ),
+            age,
             app.assets.fonts.header2.clone(),
             Colors::WHITE_SMOKE,
             Position::by_left_top(30.0 + name_size.x, 20.0),
@@ -109,15 +113,21 @@ impl BodyView {
             sprites.push(Box::new(disp));
         });
         let mut y = 0;
-        if let Some(item) = avatar.body.parts.get(&Point::new(0, 0)) {
-            y += 35;
+        for path in avatar.body.paths() {
+            let item = avatar.body.get_part(&path).unwrap();
+            y += 20;
+            let indent = 15.0 * path.names.len() as f32;
             let mut name = item.name.clone();
             name.push(':');
             sprites.push(Box::new(Label::new(
                 name,
-                app.assets.fonts.default2.clone(),
-                Colors::LIGHT_GRAY,
-                Position::by_left_top(20.0, 60.0 + y as f32),
+                app.assets.fonts.default.clone(),
+                if avatar.body.is_misplaced(&path) {
+                    Colors::LIGHT_CORAL
+                } else {
+                    Colors::LIGHT_GRAY
+                },
+                Position::by_left_top(20.0 + indent, 75.0 + y as f32),
             )));
             let color = match item.freshness() {
                 Freshness::Fresh => Colors::LIGHT_PINK,
@@ -126,11 +136,11 @@ impl BodyView {
             };
             let mut bp = Label::new(
                 item.name(),
-                app.assets.fonts.default2.clone(),
+                app.assets.fonts.default.clone(),
                 color,
-                Position::by_left_top(170.0, 60.0 + y as f32),
+                Position::by_left_top(220.0, 75.0 + y as f32),
             );
-            let size = bp.calc_size(ctx) + Vec2::new(10.0, 6.0);
+            let size = bp.calc_size(ctx) + Vec2::new(10.0, 4.0);
             sprites.push(Box::new(HoverableMesh::new(
                 Mesh::rectangle(
                     ctx,
@@ -141,7 +151,7 @@ impl BodyView {
                 Colors::TRANSPARENT,
                 Colors::WHITE_SMOKE.with_alpha(0.2),
                 size,
-                Position::by_left_top(165.0, 57.0 + y as f32),
+                Position::by_left_top(215.0, 73.0 + y as f32),
             )));
             sprites.push(Box::new(bp));
         }
@@ -393,7 +393,14 @@ impl SceneImpl for CreateCharacter {
                         HashMap::new(),
                     )
                     .init();
-                    world.save();
+                    // there is nothing to back up in the new world
+                    if let Err(error) = world.save(0) {
+                        return Some(vec![Transition::Push(Scene::LoadError(
+                            self.meta.path.clone(),
+                            error.to_string(),
+                            false,
+                        ))]);
+                    }
                     Some(vec![
                         Transition::LoadWorld(self.meta.path.clone()),
                         Transition::Replace(Scene::GameScene),
@@ -0,0 +1,72 @@
+use tetra::input::Key;
+
+use crate::{
+    app::App,
+    colors::Colors,
+    ui::{
+        Alert, Button, Horizontal, Label, Position, SomeUISprites, SomeUISpritesMut, UiSprite,
+        Vertical,
+    },
+};
+
+use super::super::{SceneImpl, Transition};
+
+/// Shown over the game when the player dies, the world doesn't go on without them
+pub struct GameOver {
+    sprites: [Box<dyn UiSprite>; 4],
+}
+
+impl GameOver {
+    pub fn new(app: &App) -> Self {
+        let alert = Box::new(Alert::new(
+            400.0,
+            170.0,
+            app.assets.alert.clone(),
+            Position::center(),
+        ));
+        let title = Box::new(Label::new(
+            "You died",
+            app.assets.fonts.header2.clone(),
+            Colors::RED,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: -55.0 },
+            },
+        ));
+        let tick = app.clone_world().borrow().meta.current_tick;
+        let message = Box::new(Label::new(
+            format!("Your story has ended on tick {}", tick),
+            app.assets.fonts.default.clone(),
+            Colors::LIGHT_YELLOW,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: -10.0 },
+            },
+        ));
+        let menu_btn = Box::new(Button::text(
+            vec![Key::Enter.into(), Key::Escape.into()],
+            "[Enter] Main menu",
+            app.assets.fonts.default.clone(),
+            app.assets.button.clone(),
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: 45.0 },
+            },
+            Transition::GoMainMenu,
+        ));
+
+        Self {
+            sprites: [alert, title, message, menu_btn],
+        }
+    }
+}
+
+impl SceneImpl for GameOver {
+    fn sprites(&self) -> SomeUISprites {
+        Some(&self.sprites)
+    }
+
+    fn sprites_mut(&mut self) -> SomeUISpritesMut {
+        Some(&mut self.sprites)
+    }
+}
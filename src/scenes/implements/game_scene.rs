@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::rc::Rc;
+use std::time::Instant;
 
 use geometry::{Direction, Point, TwoDimDirection, Vec2};
 use tetra::{
@@ -8,6 +9,7 @@ use tetra::{
         mesh::{Mesh, ShapeStyle},
         DrawParams, Rectangle,
     },
+    input::Key,
     Context,
 };
 
@@ -17,32 +19,37 @@ use crate::{
     colors::Colors,
     game::{
         map::{item::ItemView, terrain::TerrainView},
-        Action, ActionType, World,
+        Action, ActionType, LogCategory, World,
     },
+    input,
+    settings::Settings,
     ui::{GameLog, Label, Position, SomeUISprites, SomeUISpritesMut, UiSprite, Vertical},
 };
 
 use super::super::{
     game_modes::{implements::Walking, GameMode, GameModeImpl},
-    SceneImpl, SomeTransitions,
+    Scene, SceneImpl, SomeTransitions, Transition,
 };
 
 pub struct GameScene {
-    sprites: [Box<dyn UiSprite>; 2],
+    sprites: [Box<dyn UiSprite>; 3],
     pub world: Rc<RefCell<World>>,
     pub modes: Vec<Rc<RefCell<GameMode>>>,
     pub cursor: Mesh,
     pub log: GameLog,
     pub shift_of_view: Point,
+    pub progress_bar: Mesh,
     pub assets: Rc<Assets>,
     pub window_size: (i32, i32),
+    /// Real time and game tick of the last save, for autosaving
+    saved_at: (Instant, u128),
 }
 
 impl GameScene {
     pub fn new(app: &App, ctx: &mut Context) -> Self {
         let world = app.clone_world();
         let name_label = Box::new(Label::new(
-            world.borrow().player().person().mind.name.as_str(),
+            world.borrow().player().person().unwrap().mind.name.as_str(),
             app.assets.fonts.header2.clone(),
             Colors::WHITE_SMOKE,
             Position::by_left_top(50.0, 1.0),
@@ -53,8 +60,14 @@ impl GameScene {
             Colors::WHITE_SMOKE,
             Position::horizontal_center(0.0, Vertical::ByTop { y: 5.0 }),
         ));
+        let stamina_label = Box::new(Label::new(
+            Self::stamina_text(&world.borrow()),
+            app.assets.fonts.default2.clone(),
+            Colors::WHITE_SMOKE,
+            Position::by_left_top(50.0, 30.0),
+        ));
         Self {
-            sprites: [name_label, current_time_label],
+            sprites: [name_label, current_time_label, stamina_label],
             modes: vec![Rc::new(RefCell::new(Walking::new().into()))],
             cursor: Mesh::rectangle(
                 ctx,
@@ -69,8 +82,15 @@ impl GameScene {
             .unwrap(),
             log: GameLog::new(app.assets.fonts.default.font.clone()),
             shift_of_view: Point::default(),
+            progress_bar: Mesh::rectangle(
+                ctx,
+                ShapeStyle::Fill,
+                Rectangle::new(0.0, 0.0, 1.0, 1.0),
+            )
+            .unwrap(),
             assets: app.assets.clone(),
             window_size: app.window_size,
+            saved_at: (Instant::now(), world.borrow().meta.current_tick),
             world,
         }
     }
@@ -90,7 +110,7 @@ impl GameScene {
 
     pub fn try_rotate_player(&mut self, dir: Direction) {
         if let Ok(dir) = TwoDimDirection::try_from(dir) {
-            self.world.borrow_mut().player_mut().vision = dir;
+            self.world.borrow_mut().rotate_avatar(0, dir);
         }
     }
 
@@ -106,13 +126,18 @@ impl GameScene {
         }
     }
 
-    pub fn try_start_action(&mut self, typ: ActionType) {
+    /// Returns false if the action is impossible
+    pub fn try_start_action(&mut self, typ: ActionType) -> bool {
         let action = Action::new(0, typ, &self.world.borrow());
         match action {
             Ok(action) => {
                 self.world.borrow_mut().player_mut().action = Some(action);
+                true
+            }
+            Err(msg) => {
+                self.cancel_action_msg(msg);
+                false
             }
-            Err(msg) => self.cancel_action_msg(msg),
         }
     }
 
@@ -127,23 +152,98 @@ impl GameScene {
     fn make_world_tick(&mut self, ctx: &mut Context) {
         self.world.borrow_mut().tick();
 
-        for event in self.world.borrow().log().new_events() {
-            self.log.log(event.msg.as_str(), event.category.into());
+        let world = self.world.borrow();
+        let mut danger = false;
+        for event in world.log().new_events() {
+            if world.is_perceivable(event.pos) {
+                self.log.log(event.msg.as_str(), event.category.into());
+                danger |= matches!(event.category, LogCategory::Danger);
+            }
+        }
+        drop(world);
+        if danger {
+            self.world
+                .borrow_mut()
+                .interrupt_player("You stop because of the danger");
         }
         let current_time = format!("{}", self.world.borrow().meta.current_tick);
         let window_size = self.window_size;
         self.current_time_label()
             .update(current_time, ctx, window_size);
+        let stamina = Self::stamina_text(&self.world.borrow());
+        self.stamina_label().update(stamina, ctx, window_size);
+    }
+
+    /// Save failures are shown in the log, the game goes on
+    fn save(&mut self) {
+        let backups = Settings::instance().game.backups;
+        let result = self.world.borrow_mut().save(backups);
+        if let Err(error) = result {
+            self.log.log(
+                format!("Can't save the world: {}", error),
+                LogCategory::Warning.into(),
+            );
+        }
+        self.saved_at = (Instant::now(), self.world.borrow().meta.current_tick);
+    }
+
+    fn autosave(&mut self) {
+        let (ticks, minutes) = {
+            let settings = &Settings::instance().game;
+            (settings.autosave_ticks, settings.autosave_minutes)
+        };
+        let (time, tick) = self.saved_at;
+        let ticks_passed = self.world.borrow().meta.current_tick - tick;
+        if (ticks > 0 && ticks_passed >= u128::from(ticks))
+            || (minutes > 0 && time.elapsed().as_secs() >= u64::from(minutes) * 60)
+        {
+            self.save();
+        }
+    }
+
+    fn stamina_text(world: &World) -> String {
+        format!("Stamina: {}%", world.player().stamina)
+    }
+
+    fn draw_progress_bar(&self, ctx: &mut Context, position: Vec2, width: f32, progress: f32) {
+        let position = position + Vec2::new(0.0, width);
+        self.progress_bar.draw(
+            ctx,
+            DrawParams::new()
+                .position(position)
+                .scale(Vec2::new(width, 4.0))
+                .color(Colors::BLACK),
+        );
+        self.progress_bar.draw(
+            ctx,
+            DrawParams::new()
+                .position(position)
+                .scale(Vec2::new(width * progress, 4.0))
+                .color(Colors::LIME),
+        );
     }
 
     fn current_time_label(&mut self) -> &mut Label {
         self.sprites[1].as_label().unwrap()
     }
+
+    fn stamina_label(&mut self) -> &mut Label {
+        self.sprites[2].as_label().unwrap()
+    }
 }
 
 impl SceneImpl for GameScene {
     fn on_update(&mut self, ctx: &mut Context) -> SomeTransitions {
-        if self.world.borrow().player().action.is_some() {
+        self.autosave();
+        let lost = self.world.borrow_mut().take_lost_chunks();
+        if let Some(error) = lost.first() {
+            Some(vec![Transition::Push(Scene::LostChunks(error.to_string()))])
+        } else if self.world.borrow().player().dead {
+            Some(vec![Transition::Push(Scene::GameOver)])
+        } else if self.world.borrow().player().action.is_some() {
+            if input::is_key_pressed(ctx, Key::Escape) {
+                self.world.borrow_mut().interrupt_player("You stop");
+            }
             self.make_world_tick(ctx);
 
             None
@@ -174,12 +274,25 @@ impl SceneImpl for GameScene {
         let right_bottom = center_tile + (window_size_in_tiles.0 / 2, window_size_in_tiles.1 / 2);
         world.map().load_tiles_between(left_top, right_bottom);
         for (pos, tile) in world.map().tiles_between(left_top, right_bottom) {
-            if !world.is_visible(pos) {
-                continue; // TODO: TileView struct for remembering tiles and optimizing drawing
-            }
+            let visible = world.is_visible(pos);
+            let (terrain, top_item, items_count) = if visible {
+                (
+                    tile.terrain.looks_like(),
+                    tile.top_item().map(ItemView::looks_like),
+                    tile.items.len(),
+                )
+            } else if let Some(view) = &tile.memory {
+                (
+                    view.terrain.looks_like(),
+                    view.top_sprite(),
+                    view.items.len(),
+                )
+            } else {
+                continue;
+            };
             let dx = pos.x - center_tile.x;
             let dy = pos.y - center_tile.y;
-            let this_tile_size = Tileset::get_size(tile.terrain.looks_like());
+            let this_tile_size = Tileset::get_size(terrain);
             let asset_tile_size = self.assets.tileset.tile_size as f32;
             let x_correction = -(this_tile_size.x - asset_tile_size) / 2.0 * zoom;
             let y_correction = -(this_tile_size.y - asset_tile_size) * zoom;
@@ -188,33 +301,40 @@ impl SceneImpl for GameScene {
                     (center.x + dx as f32 * tile_size + x_correction).round(),
                     (center.y + dy as f32 * tile_size + y_correction).round(),
                 ))
-                .scale(scale);
+                .scale(scale)
+                .color(if visible { Colors::WHITE } else { Colors::GRAY });
             self.assets
                 .tileset
-                .draw_region(ctx, tile.terrain.looks_like(), params.clone());
-            if let Some(item) = tile.top_item() {
-                self.assets
-                    .tileset
-                    .draw_region(ctx, item.looks_like(), params.clone());
-                if tile.items.len() > 1 {
+                .draw_region(ctx, terrain, params.clone());
+            if let Some(item) = top_item {
+                self.assets.tileset.draw_region(ctx, item, params.clone());
+                if items_count > 1 {
                     self.assets.tileset.draw_region(ctx, "highlight", params);
                 }
             }
-            // TODO: multitile units
+            if !visible {
+                continue;
+            }
             let position = Vec2::new(
                 center.x + dx as f32 * tile_size,
                 center.y + dy as f32 * tile_size,
             );
             for i in tile.units.iter().copied() {
                 let unit = world.get_unit(i);
-                unit.draw(ctx, &self.assets.tileset, position, zoom, true);
+                if unit.pos == pos {
+                    unit.draw(ctx, &self.assets.tileset, position, zoom, true);
+                } else {
+                    unit.draw_segment(ctx, &self.assets.tileset, position, zoom);
+                }
+            }
+        }
+        if let Some(action) = &world.player().action {
+            // short actions are finished in one frame anyway
+            if action.length > World::SPEND_LIMIT {
+                let progress = action.progress(world.meta.current_tick);
+                self.draw_progress_bar(ctx, center, tile_size, progress);
             }
         }
-        // if world.player().action.is_some() {
-        //     self.draw_action_loader(ctx, center);
-        // } else {
-        //     self.action_text = None;
-        // }
         for (delta, color) in self.current_mode().borrow().cursors(&world) {
             let delta = delta * tile_size;
             self.cursor.draw(
@@ -258,6 +378,6 @@ impl SceneImpl for GameScene {
 
 impl Drop for GameScene {
     fn drop(&mut self) {
-        self.world.borrow_mut().save();
+        self.save();
     }
 }
@@ -0,0 +1,123 @@
+use tetra::{Context, Event};
+
+use crate::{
+    app::App,
+    colors::Colors,
+    game::map::{
+        item::{ItemInteract, ItemView},
+        Item,
+    },
+    ui::{Alert, Horizontal, Label, Position, SomeUISprites, SomeUISpritesMut, UiSprite, Vertical},
+};
+
+use super::super::{
+    helpers::{back_btn, easy_back},
+    SceneImpl, SomeTransitions,
+};
+
+fn format_mass(grams: u32) -> String {
+    if grams >= 1000 {
+        format!("{:.1} kg", f64::from(grams) / 1000.0)
+    } else {
+        format!("{} g", grams)
+    }
+}
+
+pub struct Inventory {
+    sprites: Vec<Box<dyn UiSprite>>,
+}
+
+impl Inventory {
+    pub fn new(app: &App) -> Self {
+        let world = app.world.as_ref().unwrap().borrow();
+        let avatar = world.player();
+        let window_size = app.window_size;
+        let alert = Box::new(Alert::new(
+            window_size.0 as f32,
+            window_size.1 as f32,
+            app.assets.alert.clone(),
+            Position::by_left_top(0.0, 0.0),
+        ));
+        let back_btn = back_btn(
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowBottomByBottom { offset: -20.0 },
+            },
+            &app.assets,
+        );
+        let mut sprites: Vec<Box<dyn UiSprite>> = vec![alert, back_btn];
+        let mut y = 20.0;
+        let header = |sprites: &mut Vec<Box<dyn UiSprite>>, text: String, y: &mut f32| {
+            sprites.push(Box::new(Label::new(
+                text,
+                app.assets.fonts.header2.clone(),
+                Colors::LIGHT_YELLOW,
+                Position::by_left_top(20.0, *y),
+            )));
+            *y += 35.0;
+        };
+        // items inside containers are listed with indentation
+        let line = |sprites: &mut Vec<Box<dyn UiSprite>>, item: &Item, y: &mut f32| {
+            let mut items = vec![(item, 0.0)];
+            while let Some((item, indent)) = items.pop() {
+                sprites.push(Box::new(Label::new(
+                    format!("{} ({})", item.name(), format_mass(item.mass())),
+                    app.assets.fonts.default.clone(),
+                    Colors::LIGHT_GRAY,
+                    Position::by_left_top(30.0 + indent, *y),
+                )));
+                *y += 20.0;
+                if let Some(container) = item.container() {
+                    items.extend(container.items.iter().rev().map(|i| (i, indent + 15.0)));
+                }
+            }
+        };
+
+        let hands = avatar.body.capabilities().hands;
+        header(
+            &mut sprites,
+            format!("Hands ({}/{}):", avatar.wield.len(), hands),
+            &mut y,
+        );
+        for item in &avatar.wield {
+            line(&mut sprites, item, &mut y);
+        }
+        y += 10.0;
+        header(&mut sprites, "Wear:".to_string(), &mut y);
+        for item in &avatar.body.wear {
+            line(&mut sprites, item, &mut y);
+        }
+        y += 10.0;
+        let total: u32 = avatar.wield.iter().map(ItemInteract::mass).sum::<u32>()
+            + avatar.body.wear.iter().map(ItemInteract::mass).sum::<u32>();
+        header(
+            &mut sprites,
+            format!("Total carried: {}", format_mass(total)),
+            &mut y,
+        );
+
+        Self { sprites }
+    }
+
+    fn alert(&mut self) -> &mut Alert {
+        self.sprites[0].as_alert().unwrap()
+    }
+}
+
+impl SceneImpl for Inventory {
+    fn event(&mut self, _ctx: &mut Context, event: Event) -> SomeTransitions {
+        easy_back(&event, false)
+    }
+
+    fn on_resize(&mut self, ctx: &mut Context, window_size: (i32, i32)) {
+        self.alert().set_size(ctx, window_size);
+    }
+
+    fn sprites(&self) -> SomeUISprites {
+        Some(&self.sprites)
+    }
+
+    fn sprites_mut(&mut self) -> SomeUISpritesMut {
+        Some(&mut self.sprites)
+    }
+}
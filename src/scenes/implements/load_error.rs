@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use tetra::{input::Key, Context, Event};
+
+use crate::{
+    app::App,
+    colors::Colors,
+    ui::{
+        Alert, Button, Horizontal, Label, Position, SomeUISprites, SomeUISpritesMut, UiSprite,
+        Vertical,
+    },
+};
+
+use super::super::{helpers::easy_back, Scene, SceneImpl, SomeTransitions, Transition};
+
+/// Shown over the load screen when the savefile is broken
+pub struct LoadError {
+    path: PathBuf,
+    sprites: Vec<Box<dyn UiSprite>>,
+}
+
+impl LoadError {
+    pub fn new(path: PathBuf, error: &str, salvage: bool, app: &App) -> Self {
+        let alert = Box::new(Alert::new(
+            700.0,
+            170.0,
+            app.assets.alert.clone(),
+            Position::center(),
+        ));
+        let title = Box::new(Label::new(
+            "Can't load the world",
+            app.assets.fonts.header2.clone(),
+            Colors::LIGHT_YELLOW,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: -55.0 },
+            },
+        ));
+        let message = Box::new(Label::new(
+            error,
+            app.assets.fonts.default.clone(),
+            Colors::RED,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: -10.0 },
+            },
+        ));
+        let back_btn = Box::new(Button::text(
+            vec![Key::Escape.into()],
+            "[Esc] Back",
+            app.assets.fonts.default.clone(),
+            app.assets.button.clone(),
+            Position {
+                x: Horizontal::AtWindowCenterByCenter {
+                    offset: if salvage { -100.0 } else { 0.0 },
+                },
+                y: Vertical::AtWindowCenterByCenter { offset: 45.0 },
+            },
+            Transition::Pop,
+        ));
+        let mut sprites: Vec<Box<dyn UiSprite>> = vec![alert, title, message, back_btn];
+        if salvage {
+            sprites.push(Box::new(Button::text(
+                vec![Key::S.into()],
+                "[S] Salvage",
+                app.assets.fonts.default.clone(),
+                app.assets.button.clone(),
+                Position {
+                    x: Horizontal::AtWindowCenterByCenter { offset: 100.0 },
+                    y: Vertical::AtWindowCenterByCenter { offset: 45.0 },
+                },
+                Transition::CustomEvent(0),
+            )));
+        }
+
+        Self { path, sprites }
+    }
+}
+
+impl SceneImpl for LoadError {
+    fn event(&mut self, _ctx: &mut Context, event: Event) -> SomeTransitions {
+        easy_back(&event, false)
+    }
+
+    fn sprites(&self) -> SomeUISprites {
+        Some(&self.sprites)
+    }
+
+    fn sprites_mut(&mut self) -> SomeUISpritesMut {
+        Some(&mut self.sprites)
+    }
+
+    fn custom_event(&mut self, _ctx: &mut Context, _event: u8) -> SomeTransitions {
+        // broken chunks are generated again, lost units and log are gone
+        Some(vec![
+            Transition::Pop,
+            Transition::SalvageWorld(self.path.clone()),
+            Transition::Replace(Scene::GameScene),
+        ])
+    }
+}
@@ -14,7 +14,7 @@ use time::{format_description::FormatItem, OffsetDateTime};
 use crate::{
     app::App,
     colors::Colors,
-    savefile::{self, savefiles, savefiles_exists, Meta},
+    savefile::{self, savefiles, savefiles_exists, BrokenSave, Meta},
     settings::Settings,
     ui::{
         Alert, Button, Horizontal, HoverableMesh, Label, Position, Positionate, SomeUISprites,
@@ -72,18 +72,31 @@ impl LoadWorld {
 
         Self {
             sprites,
-            paths: savefiles.into_iter().map(|s| s.path).collect(),
+            paths: savefiles
+                .into_iter()
+                .map(|s| match s {
+                    Ok(meta) => meta.path,
+                    Err(broken) => broken.path,
+                })
+                .collect(),
         }
     }
 
     fn push_sprites_for_savefile(
         sprites: &mut Sprites,
         i: usize,
-        savefile: &Meta,
+        savefile: &Result<Meta, BrokenSave>,
         y: f32,
         ctx: &mut Context,
         app: &App,
     ) {
+        let name = match savefile {
+            Ok(meta) => meta.name.clone(),
+            Err(broken) => broken
+                .path
+                .file_stem()
+                .map_or_else(String::new, |s| s.to_string_lossy().to_string()),
+        };
         sprites.push(Box::new(HoverableMesh::new(
             Mesh::rectangle(ctx, ShapeStyle::Fill, Rectangle::new(0.0, 0.0, 564.0, 50.0)).unwrap(),
             if i % 2 == 1 {
@@ -99,7 +112,7 @@ impl LoadWorld {
             },
         )));
         sprites.push(Box::new(Label::new(
-            savefile.name.as_str(),
+            name,
             app.assets.fonts.header2.clone(),
             Colors::LIGHT_YELLOW,
             Position {
@@ -107,6 +120,50 @@ impl LoadWorld {
                 y: Vertical::AtWindowCenterByTop { offset: y - 2.0 },
             },
         )));
+        match savefile {
+            Ok(savefile) => Self::push_sprites_for_meta(sprites, i, savefile, y, ctx, app),
+            Err(broken) => {
+                sprites.push(Box::new(Label::new(
+                    broken.error.to_string(),
+                    app.assets.fonts.default.clone(),
+                    Colors::RED,
+                    Position {
+                        x: Horizontal::AtWindowCenterByLeft { offset: -275.0 },
+                        y: Vertical::AtWindowCenterByTop { offset: y + 30.0 },
+                    },
+                )));
+            }
+        }
+        sprites.push(Box::new(Button::text(
+            if i < 10 {
+                vec![(KEYS[i], KeyModifier::Alt).into()]
+            } else {
+                vec![]
+            },
+            if i < 10 {
+                format!("[Alt+{}] Delete", if i < 9 { i + 1 } else { 0 })
+            } else {
+                "Delete".to_string()
+            },
+            app.assets.fonts.default.clone(),
+            app.assets.button.clone(),
+            Position {
+                x: Horizontal::AtWindowCenterByRight { offset: 275.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: y + 24.5 },
+            },
+            Transition::CustomEvent((i * 2 + 1) as u8),
+        )));
+    }
+
+    /// Version, time of the last save and a button to load it
+    fn push_sprites_for_meta(
+        sprites: &mut Sprites,
+        i: usize,
+        savefile: &Meta,
+        y: f32,
+        ctx: &mut Context,
+        app: &App,
+    ) {
         let mut version_label = Box::new(Label::new(
             savefile.version.as_str(),
             app.assets.fonts.default.clone(),
@@ -149,25 +206,6 @@ impl LoadWorld {
             },
             Transition::CustomEvent((i * 2) as u8),
         )));
-        sprites.push(Box::new(Button::text(
-            if i < 10 {
-                vec![(KEYS[i], KeyModifier::Alt).into()]
-            } else {
-                vec![]
-            },
-            if i < 10 {
-                format!("[Alt+{}] Delete", if i < 9 { i + 1 } else { 0 })
-            } else {
-                "Delete".to_string()
-            },
-            app.assets.fonts.default.clone(),
-            app.assets.button.clone(),
-            Position {
-                x: Horizontal::AtWindowCenterByRight { offset: 275.0 },
-                y: Vertical::AtWindowCenterByCenter { offset: y + 24.5 },
-            },
-            Transition::CustomEvent((i * 2 + 1) as u8),
-        )));
     }
 }
 
@@ -189,17 +227,24 @@ impl SceneImpl for LoadWorld {
         let path = self.paths.get(i)?;
         if event % 2 == 0 {
             // load
-            if let Some(meta) = savefile::load(path) {
-                if savefile::has_avatar(path) {
-                    Some(vec![
-                        Transition::LoadWorld(path.clone()),
-                        Transition::Replace(Scene::GameScene),
-                    ])
-                } else {
-                    Some(vec![Transition::Replace(Scene::CreateCharacter(meta.path))])
+            let meta =
+                savefile::load(path).and_then(|meta| savefile::check_units(&meta).map(|_| meta));
+            match meta {
+                Ok(meta) => {
+                    if savefile::has_avatar(path) {
+                        Some(vec![
+                            Transition::LoadWorld(path.clone()),
+                            Transition::Replace(Scene::GameScene),
+                        ])
+                    } else {
+                        Some(vec![Transition::Replace(Scene::CreateCharacter(meta.path))])
+                    }
                 }
-            } else {
-                panic!("Can't load savefile: {:?}", path)
+                Err(err) => Some(vec![Transition::Push(Scene::LoadError(
+                    path.clone(),
+                    err.to_string(),
+                    false,
+                ))]),
             }
         } else {
             // delete
@@ -0,0 +1,80 @@
+use tetra::input::Key;
+
+use crate::{
+    app::App,
+    colors::Colors,
+    ui::{
+        Alert, Button, Horizontal, Label, Position, SomeUISprites, SomeUISpritesMut, UiSprite,
+        Vertical,
+    },
+};
+
+use super::super::{SceneImpl, Transition};
+
+/// Shown over the game when a region file read while playing is broken,
+/// its broken chunks are generated again and the file is kept aside
+pub struct LostChunks {
+    sprites: [Box<dyn UiSprite>; 5],
+}
+
+impl LostChunks {
+    pub fn new(error: &str, app: &App) -> Self {
+        let alert = Box::new(Alert::new(
+            700.0,
+            200.0,
+            app.assets.alert.clone(),
+            Position::center(),
+        ));
+        let title = Box::new(Label::new(
+            "Part of the world is broken",
+            app.assets.fonts.header2.clone(),
+            Colors::LIGHT_YELLOW,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: -70.0 },
+            },
+        ));
+        let message = Box::new(Label::new(
+            error,
+            app.assets.fonts.default.clone(),
+            Colors::RED,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: -25.0 },
+            },
+        ));
+        let hint = Box::new(Label::new(
+            "Broken chunks are generated again, the old file is kept with .broken extension",
+            app.assets.fonts.default.clone(),
+            Colors::LIGHT_YELLOW,
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: 5.0 },
+            },
+        ));
+        let continue_btn = Box::new(Button::text(
+            vec![Key::Enter.into(), Key::Escape.into()],
+            "[Enter] Continue",
+            app.assets.fonts.default.clone(),
+            app.assets.button.clone(),
+            Position {
+                x: Horizontal::AtWindowCenterByCenter { offset: 0.0 },
+                y: Vertical::AtWindowCenterByCenter { offset: 60.0 },
+            },
+            Transition::Pop,
+        ));
+        Self {
+            sprites: [alert, title, message, hint, continue_btn],
+        }
+    }
+}
+
+impl SceneImpl for LostChunks {
+    fn sprites(&self) -> SomeUISprites {
+        Some(&self.sprites)
+    }
+
+    fn sprites_mut(&mut self) -> SomeUISpritesMut {
+        Some(&mut self.sprites)
+    }
+}
@@ -3,8 +3,12 @@ pub use create_character::CreateCharacter;
 pub use create_world::CreateWorld;
 pub use empty::Empty;
 pub use game_menu::GameMenu;
+pub use game_over::GameOver;
 pub use game_scene::GameScene;
+pub use inventory::Inventory;
+pub use load_error::LoadError;
 pub use load_world::LoadWorld;
+pub use lost_chunks::LostChunks;
 pub use main_menu::MainMenu;
 pub use settings_scene::SettingsScene;
 
@@ -13,7 +17,11 @@ mod create_character;
 mod create_world;
 mod empty;
 mod game_menu;
+mod game_over;
 mod game_scene;
+mod inventory;
+mod load_error;
 mod load_world;
+mod lost_chunks;
 mod main_menu;
 mod settings_scene;
@@ -6,8 +6,8 @@ use crate::app::App;
 
 use super::{
     implements::{
-        BodyView, CreateCharacter, CreateWorld, Empty, GameMenu, GameScene, LoadWorld, MainMenu,
-        SettingsScene,
+        BodyView, CreateCharacter, CreateWorld, Empty, GameMenu, GameOver, GameScene, Inventory,
+        LoadError, LoadWorld, LostChunks, MainMenu, SettingsScene,
     },
     SceneImpl,
 };
@@ -20,10 +20,14 @@ pub enum Scene {
     Settings,
     CreateWorld,
     LoadWorld,
+    LoadError(PathBuf, String, bool), // path, error, offer to salvage
     CreateCharacter(PathBuf),
     GameScene,
     GameMenu,
+    GameOver,
+    LostChunks(String),
     BodyView(usize),
+    Inventory,
 }
 
 impl Scene {
@@ -35,10 +39,16 @@ impl Scene {
             Scene::Settings => Box::new(SettingsScene::new(app, ctx)),
             Scene::CreateWorld => Box::new(CreateWorld::new(app, ctx)),
             Scene::LoadWorld => Box::new(LoadWorld::new(app, ctx)),
+            Scene::LoadError(path, error, salvage) => {
+                Box::new(LoadError::new(path, error.as_str(), salvage, app))
+            }
             Scene::CreateCharacter(path) => Box::new(CreateCharacter::new(&path, app, ctx)),
             Scene::GameScene => Box::new(GameScene::new(app, ctx)),
             Scene::GameMenu => Box::new(GameMenu::new(app)),
+            Scene::GameOver => Box::new(GameOver::new(app)),
+            Scene::LostChunks(error) => Box::new(LostChunks::new(error.as_str(), app)),
             Scene::BodyView(unit_id) => Box::new(BodyView::new(unit_id, app, ctx)),
+            Scene::Inventory => Box::new(Inventory::new(app)),
         }
     }
 }
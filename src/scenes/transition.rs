@@ -9,6 +9,7 @@ pub enum Transition {
     GoMainMenu,     // unload world and pop to first scene
     Replace(Scene), // pop and push
     LoadWorld(PathBuf),
+    SalvageWorld(PathBuf), // load what is readable from a broken savefile
     CustomEvent(u8),
     Quit,
 }
@@ -46,14 +46,24 @@ pub struct Debug {
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[serde(default)]
 pub struct Game {
     pub repeat_interval: u32,
+    /// Previous versions of the savefile kept for every world
+    pub backups: usize,
+    /// Autosave after this count of game ticks, 0 to disable
+    pub autosave_ticks: u32,
+    /// Autosave after this count of real time minutes, 0 to disable
+    pub autosave_minutes: u32,
 }
 
 impl Default for Game {
     fn default() -> Self {
         Self {
             repeat_interval: 125,
+            backups: 3,
+            autosave_ticks: 3000,
+            autosave_minutes: 5,
         }
     }
 }